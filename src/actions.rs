@@ -0,0 +1,668 @@
+use crate::app::{ActivePane, App, GitLink};
+use crate::layout::Pane;
+use crate::network::{BatchAction, Status};
+
+/// Context a key binding applies in
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Scope {
+    Global,
+    /// Deployment list focused
+    List,
+    /// Logs pane focused
+    Logs,
+    /// Full-screen log viewer
+    LogViewer,
+    /// Breakdown, dashboard, branch matrix and compare views, the selectors, the
+    /// context menu and the help overlay
+    Overlay,
+}
+
+/// Sections of the help overlay
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HelpSection {
+    General,
+    Deployments,
+    Logs,
+    LogViewer,
+    Prompts,
+    Selectors,
+    ContextMenu,
+    Views,
+}
+
+impl HelpSection {
+    pub fn all() -> &'static [HelpSection] {
+        use HelpSection::*;
+        &[
+            General,
+            Deployments,
+            Logs,
+            LogViewer,
+            Prompts,
+            Selectors,
+            ContextMenu,
+            Views,
+        ]
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            HelpSection::General => "General",
+            HelpSection::Deployments => "Deployment list",
+            HelpSection::Logs => "Logs",
+            HelpSection::LogViewer => "Full-screen log viewer",
+            HelpSection::Prompts => "Filter & search prompts",
+            HelpSection::Selectors => "Theme & project selectors",
+            HelpSection::ContextMenu => "Context menu",
+            HelpSection::Views => "Breakdown, dashboard, branch matrix & compare views",
+        }
+    }
+}
+
+/// Everything that can be triggered from the deployments view, by key or from the
+/// command palette. Both run through the same code path in `main.rs`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Action {
+    // Navigation
+    Up,
+    Down,
+    Top,
+    Bottom,
+    FocusLogs,
+    FocusList,
+    Select,
+    // Deployments
+    Redeploy,
+    Cancel,
+    OpenBrowser,
+    OpenCommit,
+    OpenBranch,
+    OpenPullRequest,
+    CopyMenu,
+    CopyUrl,
+    CopyId,
+    CopyCommitMsg,
+    CopyLogLines,
+    Compare,
+    Mark,
+    MarkRange,
+    MarkQueued,
+    ClearMarks,
+    Filter,
+    CycleSort,
+    GroupTree,
+    FoldGroup,
+    FoldAllGroups,
+    // Logs
+    LogViewer,
+    LogSearch,
+    NextHit,
+    PrevHit,
+    JumpToLine,
+    Follow,
+    JumpToError,
+    ProblemsOnly,
+    InstallNoise,
+    NativeColors,
+    TimeMode,
+    FoldSection,
+    FoldAllSections,
+    LogRange,
+    OpenInEditor,
+    ExportLogs,
+    ExportLogsWithTimestamps,
+    // Views & settings
+    Projects,
+    Breakdown,
+    Dashboard,
+    Branches,
+    StatPeriod,
+    CustomPeriod,
+    Layout,
+    Themes,
+    Mouse,
+    Legend,
+    Palette,
+    Help,
+    Quit,
+    // Views & overlays
+    /// Sort a table view by its nth column (1-based)
+    SortColumn(u8),
+    CycleGroup,
+    PageUp,
+    PageDown,
+    Transparency,
+}
+
+impl Action {
+    pub fn all() -> &'static [Action] {
+        use Action::*;
+        &[
+            Up,
+            Down,
+            Top,
+            Bottom,
+            FocusLogs,
+            FocusList,
+            Select,
+            Redeploy,
+            Cancel,
+            OpenBrowser,
+            OpenCommit,
+            OpenBranch,
+            OpenPullRequest,
+            CopyMenu,
+            CopyUrl,
+            CopyId,
+            CopyCommitMsg,
+            CopyLogLines,
+            Compare,
+            Mark,
+            MarkRange,
+            MarkQueued,
+            ClearMarks,
+            Filter,
+            CycleSort,
+            GroupTree,
+            FoldGroup,
+            FoldAllGroups,
+            LogViewer,
+            LogSearch,
+            NextHit,
+            PrevHit,
+            JumpToLine,
+            Follow,
+            JumpToError,
+            ProblemsOnly,
+            InstallNoise,
+            NativeColors,
+            TimeMode,
+            FoldSection,
+            FoldAllSections,
+            LogRange,
+            OpenInEditor,
+            ExportLogs,
+            ExportLogsWithTimestamps,
+            Projects,
+            Breakdown,
+            Dashboard,
+            Branches,
+            StatPeriod,
+            CustomPeriod,
+            Layout,
+            Themes,
+            Mouse,
+            Legend,
+            Palette,
+            Help,
+            Quit,
+            SortColumn(1),
+            SortColumn(2),
+            SortColumn(3),
+            SortColumn(4),
+            SortColumn(5),
+            SortColumn(6),
+            CycleGroup,
+            PageUp,
+            PageDown,
+            Transparency,
+        ]
+    }
+
+    /// Stable name, used to remember recent commands in the config
+    pub fn id(&self) -> &'static str {
+        use Action::*;
+        match self {
+            Up => "up",
+            Down => "down",
+            Top => "top",
+            Bottom => "bottom",
+            FocusLogs => "focus_logs",
+            FocusList => "focus_list",
+            Select => "select",
+            Redeploy => "redeploy",
+            Cancel => "cancel",
+            OpenBrowser => "open_browser",
+            OpenCommit => "open_commit",
+            OpenBranch => "open_branch",
+            OpenPullRequest => "open_pull_request",
+            CopyMenu => "copy_menu",
+            CopyUrl => "copy_url",
+            CopyId => "copy_id",
+            CopyCommitMsg => "copy_commit_message",
+            CopyLogLines => "copy_log_lines",
+            Compare => "compare",
+            Mark => "mark",
+            MarkRange => "mark_range",
+            MarkQueued => "mark_queued",
+            ClearMarks => "clear_marks",
+            Filter => "filter",
+            CycleSort => "cycle_sort",
+            GroupTree => "group_tree",
+            FoldGroup => "fold_group",
+            FoldAllGroups => "fold_all_groups",
+            LogViewer => "log_viewer",
+            LogSearch => "log_search",
+            NextHit => "next_hit",
+            PrevHit => "prev_hit",
+            JumpToLine => "jump_to_line",
+            Follow => "follow",
+            JumpToError => "jump_to_error",
+            ProblemsOnly => "problems_only",
+            InstallNoise => "install_noise",
+            NativeColors => "native_colors",
+            TimeMode => "time_mode",
+            FoldSection => "fold_section",
+            FoldAllSections => "fold_all_sections",
+            LogRange => "log_range",
+            OpenInEditor => "open_in_editor",
+            ExportLogs => "export_logs",
+            ExportLogsWithTimestamps => "export_logs_with_timestamps",
+            Projects => "projects",
+            Breakdown => "breakdown",
+            Dashboard => "dashboard",
+            Branches => "branches",
+            StatPeriod => "stat_period",
+            CustomPeriod => "custom_period",
+            Layout => "layout",
+            Themes => "themes",
+            Mouse => "mouse",
+            Legend => "legend",
+            Palette => "palette",
+            Help => "help",
+            Quit => "quit",
+            SortColumn(1) => "sort_column_1",
+            SortColumn(2) => "sort_column_2",
+            SortColumn(3) => "sort_column_3",
+            SortColumn(4) => "sort_column_4",
+            SortColumn(5) => "sort_column_5",
+            SortColumn(_) => "sort_column_6",
+            CycleGroup => "cycle_group",
+            PageUp => "page_up",
+            PageDown => "page_down",
+            Transparency => "transparency",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Action> {
+        Action::all().iter().copied().find(|a| a.id() == id)
+    }
+
+    /// Title shown in the command palette
+    pub fn title(&self) -> &'static str {
+        use Action::*;
+        match self {
+            Up => "Move up",
+            Down => "Move down",
+            Top => "Go to top",
+            Bottom => "Go to bottom",
+            FocusLogs => "Focus logs",
+            FocusList => "Focus deployment list",
+            Select => "Load logs / fold group",
+            Redeploy => "Redeploy selected or marked",
+            Cancel => "Cancel selected or marked build",
+            OpenBrowser => "Open deployment in browser",
+            OpenCommit => "Open commit",
+            OpenBranch => "Open branch",
+            OpenPullRequest => "Open pull request",
+            CopyMenu => "Copy URL / ID / commit message",
+            CopyUrl => "Copy deployment URL",
+            CopyId => "Copy deployment ID",
+            CopyCommitMsg => "Copy commit message",
+            CopyLogLines => "Copy selected log lines",
+            Compare => "Compare deployments",
+            Mark => "Mark deployment",
+            MarkRange => "Mark range",
+            MarkQueued => "Mark queued builds of this branch",
+            ClearMarks => "Clear marks",
+            Filter => "Filter deployments",
+            CycleSort => "Cycle sort order",
+            GroupTree => "Toggle project/branch tree",
+            FoldGroup => "Fold group",
+            FoldAllGroups => "Fold all groups",
+            LogViewer => "Toggle full-screen log viewer",
+            LogSearch => "Search logs",
+            NextHit => "Next search match",
+            PrevHit => "Previous search match",
+            JumpToLine => "Jump to log line",
+            Follow => "Toggle log follow",
+            JumpToError => "Jump to first error",
+            ProblemsOnly => "Toggle warnings & errors only",
+            InstallNoise => "Toggle install output",
+            NativeColors => "Toggle native log colors",
+            TimeMode => "Cycle log timestamps",
+            FoldSection => "Fold log section",
+            FoldAllSections => "Fold all log sections",
+            LogRange => "Select log line range",
+            OpenInEditor => "Open file in editor",
+            ExportLogs => "Save log to file",
+            ExportLogsWithTimestamps => "Save log with timestamps",
+            Projects => "Switch project",
+            Breakdown => "Breakdown view",
+            Dashboard => "Dashboard",
+            Branches => "Branch matrix",
+            StatPeriod => "Cycle stats period",
+            CustomPeriod => "Custom stats period",
+            Layout => "Cycle layout",
+            Themes => "Change theme",
+            Mouse => "Toggle mouse",
+            Legend => "Toggle key legend",
+            Palette => "Command palette",
+            Help => "Help",
+            Quit => "Quit",
+            SortColumn(_) => "Sort by column",
+            CycleGroup => "Cycle breakdown grouping",
+            PageUp => "Page up",
+            PageDown => "Page down",
+            Transparency => "Toggle background transparency",
+        }
+    }
+
+    /// One-line explanation for the help overlay
+    pub fn description(&self) -> &'static str {
+        use Action::*;
+        match self {
+            Up => "Select the previous deployment or log line",
+            Down => "Select the next deployment or log line",
+            Top => "Jump to the first row",
+            Bottom => "Jump to the last row; in logs of a running build, resume following",
+            FocusLogs => "Move focus to the logs (full screen in compact mode)",
+            FocusList => "Move focus back to the deployment list",
+            Select => "Load the selected deployment's logs, or fold a group header",
+            Redeploy => "Redeploy the selection from the same commit, or every marked deployment",
+            Cancel => "Cancel the selected build, or every marked queued/running build",
+            OpenBrowser => "Open the deployment URL in the browser",
+            OpenCommit => "Open the commit on GitHub, GitLab or Bitbucket",
+            OpenBranch => "Open the branch on the git provider",
+            OpenPullRequest => "Open the pull request on the git provider",
+            CopyMenu => "Copy the URL, ID, commit message or selected log lines",
+            CopyUrl => "Copy the deployment URL to the clipboard",
+            CopyId => "Copy the deployment ID to the clipboard",
+            CopyCommitMsg => "Copy the commit message to the clipboard",
+            CopyLogLines => "Copy the selected log line or range to the clipboard",
+            Compare => "Pick a base deployment, then compare it with another one",
+            Mark => "Mark or unmark the deployment for a batch action",
+            MarkRange => "Start or add a range of deployments to the marks",
+            MarkQueued => "Mark every queued or running preview build of the branch",
+            ClearMarks => "Clear all marks",
+            Filter => "Filter the list with the filter syntax",
+            CycleSort => "Order by newest, duration, status, project or branch",
+            GroupTree => "Nest the list under project and branch headers",
+            FoldGroup => "Fold or unfold the selected group",
+            FoldAllGroups => "Fold every project, or unfold everything",
+            LogViewer => "Show the logs full screen, or go back",
+            LogSearch => "Incremental regex search in the log",
+            NextHit => "Jump to the next search match",
+            PrevHit => "Jump to the previous search match",
+            JumpToLine => "Type a line number to jump to",
+            Follow => "Keep the newest log line in view while the build runs",
+            JumpToError => "Select the first error line",
+            ProblemsOnly => "Only show warnings and errors",
+            InstallNoise => "Hide dependency install output (errors are kept)",
+            NativeColors => "Switch between the build's colors and Polymer's highlighting",
+            TimeMode => "Cycle the timestamp gutter: off, clock, since start, delta",
+            FoldSection => "Fold or unfold the log section under the cursor",
+            FoldAllSections => "Fold or unfold every log section",
+            LogRange => "Start or clear a range of log lines to copy",
+            OpenInEditor => "Open the file referenced on the line in $EDITOR",
+            ExportLogs => "Save the full log to a file in the current directory",
+            ExportLogsWithTimestamps => "Save the full log with timestamps",
+            Projects => "Switch to another project",
+            Breakdown => "Stats per branch, author and target",
+            Dashboard => "Health of every project",
+            Branches => "Latest deployment per branch",
+            StatPeriod => "Cycle the statistics period",
+            CustomPeriod => "Type a custom statistics period",
+            Layout => "Cycle the pane layout",
+            Themes => "Pick a color theme",
+            Mouse => "Toggle mouse support",
+            Legend => "Show or hide the key legend",
+            Palette => "Search and run any command",
+            Help => "Show this help",
+            Quit => "Quit Polymer",
+            SortColumn(1) => "Sort by the first column, again to reverse (breakdown, dashboard)",
+            SortColumn(2) => "Sort by the second column",
+            SortColumn(3) => "Sort by the third column",
+            SortColumn(4) => "Sort by the fourth column",
+            SortColumn(5) => "Sort by the fifth column",
+            SortColumn(_) => "Sort by the sixth column (dashboard)",
+            CycleGroup => "Cycle the breakdown grouping: branch, author, target",
+            PageUp => "Scroll the compare view or help up a page",
+            PageDown => "Scroll the compare view or help down a page",
+            Transparency => "Toggle background transparency (theme selector)",
+        }
+    }
+
+    /// Actions that only run after a second press of their key
+    pub fn needs_confirmation(&self) -> bool {
+        matches!(self, Action::Redeploy | Action::Cancel)
+    }
+
+    pub fn help_section(&self) -> HelpSection {
+        use Action::*;
+        match self.scope() {
+            Scope::List => HelpSection::Deployments,
+            Scope::Logs => HelpSection::Logs,
+            Scope::LogViewer => HelpSection::LogViewer,
+            Scope::Overlay => match self {
+                Transparency => HelpSection::Selectors,
+                _ => HelpSection::Views,
+            },
+            Scope::Global => match self {
+                OpenBrowser | OpenCommit | OpenBranch | OpenPullRequest | CopyMenu | CopyUrl
+                | CopyId | CopyCommitMsg | Compare | MarkQueued | ClearMarks | Filter
+                | CycleSort | GroupTree | Select => HelpSection::Deployments,
+                LogViewer
+                | CopyLogLines
+                | Follow
+                | JumpToError
+                | ProblemsOnly
+                | InstallNoise
+                | NativeColors
+                | TimeMode
+                | ExportLogs
+                | ExportLogsWithTimestamps => HelpSection::Logs,
+                _ => HelpSection::General,
+            },
+        }
+    }
+
+    /// Keys used when the config's `keymap` doesn't mention the action
+    pub fn default_keys(&self) -> &'static [&'static str] {
+        use Action::*;
+        match self {
+            Up => &["up", "k"],
+            Down => &["down", "j"],
+            Top => &["g"],
+            Bottom => &["G"],
+            FocusLogs => &["right", "l"],
+            FocusList => &["left", "h"],
+            Select => &["enter"],
+            Redeploy => &["r"],
+            Cancel => &["K"],
+            OpenBrowser => &["o"],
+            OpenCommit => &["c"],
+            OpenBranch => &["b"],
+            OpenPullRequest => &["P"],
+            CopyMenu => &["y"],
+            CopyUrl | CopyId | CopyCommitMsg | CopyLogLines => &[],
+            Compare => &["C"],
+            Mark => &["x"],
+            MarkRange => &["V"],
+            MarkQueued => &["X"],
+            ClearMarks => &["u"],
+            Filter => &["/"],
+            CycleSort => &[">"],
+            GroupTree => &["="],
+            FoldGroup => &["z"],
+            FoldAllGroups => &["Z"],
+            LogViewer => &["f"],
+            LogSearch => &["/"],
+            NextHit => &["n"],
+            PrevHit => &["N"],
+            JumpToLine => &[":"],
+            Follow => &["F"],
+            JumpToError => &["E"],
+            ProblemsOnly => &["v"],
+            InstallNoise => &["i"],
+            NativeColors => &["a"],
+            TimeMode => &["T"],
+            FoldSection => &["z"],
+            FoldAllSections => &["Z"],
+            LogRange => &["V"],
+            OpenInEditor => &["e"],
+            ExportLogs => &["w"],
+            ExportLogsWithTimestamps => &["W"],
+            Projects => &["p"],
+            Breakdown => &["A"],
+            Dashboard => &["D"],
+            Branches => &["B"],
+            StatPeriod => &["s"],
+            CustomPeriod => &["S"],
+            Layout => &["L"],
+            Themes => &["t"],
+            Mouse => &["m"],
+            Legend => &["space"],
+            Palette => &[":", "ctrl-p"],
+            Help => &["?", "f1"],
+            Quit => &["q"],
+            SortColumn(1) => &["1"],
+            SortColumn(2) => &["2"],
+            SortColumn(3) => &["3"],
+            SortColumn(4) => &["4"],
+            SortColumn(5) => &["5"],
+            SortColumn(_) => &["6"],
+            CycleGroup => &["tab"],
+            PageUp => &["pgup"],
+            PageDown => &["pgdn"],
+            Transparency => &["x"],
+        }
+    }
+
+    /// Where the action's keys apply. Only the list or the logs pane is focused at a
+    /// time; the full-screen log viewer also counts as the logs pane.
+    pub fn scope(&self) -> Scope {
+        use Action::*;
+        match self {
+            Redeploy | Cancel | Mark | MarkRange | FoldGroup | FoldAllGroups => Scope::List,
+            LogRange | OpenInEditor | FoldSection | FoldAllSections => Scope::Logs,
+            LogSearch | NextHit | PrevHit | JumpToLine => Scope::LogViewer,
+            SortColumn(_) | CycleGroup | PageUp | PageDown | Transparency => Scope::Overlay,
+            _ => Scope::Global,
+        }
+    }
+
+    /// Cursor movement, the palette itself and keys that only work inside the views
+    /// aren't listed in the palette
+    pub fn in_palette(&self) -> bool {
+        use Action::*;
+        !matches!(
+            self,
+            Up | Down | Top | Bottom | Palette | SortColumn(_) | CycleGroup | PageUp | PageDown
+        )
+    }
+
+    /// Whether the action does anything for the current selection and state
+    pub fn available(&self, app: &App) -> bool {
+        use Action::*;
+        let has_deployment = app.selected_deployment().is_some();
+        match self {
+            FocusLogs => app.is_compact || app.layout().contains(Pane::Logs),
+            FocusList => app.active_pane == ActivePane::Logs,
+            Select => app._list_state.selected().is_some() && !app.list_rows.is_empty(),
+            Redeploy => has_deployment || app.mark_count() > 0,
+            Cancel => {
+                !app.batch_targets(BatchAction::Cancel).is_empty()
+                    || app
+                        .selected_deployment()
+                        .is_some_and(|d| matches!(d.status, Status::Building))
+            }
+            OpenCommit => app.selected_git_url(GitLink::Commit).is_ok(),
+            OpenBranch => app.selected_git_url(GitLink::Branch).is_ok(),
+            OpenPullRequest => app.selected_git_url(GitLink::PullRequest).is_ok(),
+            Compare => has_deployment || app.marked.len() == 2,
+            OpenBrowser | CopyMenu | CopyUrl | CopyId | CopyCommitMsg | Mark | MarkRange
+            | MarkQueued | Follow => has_deployment,
+            CopyLogLines => app.selected_log_text().is_some(),
+            ClearMarks => app.mark_count() > 0 || app.mark_anchor.is_some(),
+            FoldGroup | FoldAllGroups => app.group_tree && !app.deployment_groups.is_empty(),
+            LogSearch | JumpToLine => app.show_log_fullscreen,
+            NextHit | PrevHit => app.show_log_fullscreen && !app.log_search_hits.is_empty(),
+            JumpToError => app.failure_summary.is_some(),
+            FoldSection | FoldAllSections => !app.log_sections.is_empty(),
+            LogRange | OpenInEditor => app.selected_log_line().is_some(),
+            ExportLogs | ExportLogsWithTimestamps => !app.logs.is_empty(),
+            _ => true,
+        }
+    }
+
+    /// Short label for context menu entries
+    pub fn menu_title(&self) -> &'static str {
+        use Action::*;
+        match self {
+            OpenBrowser => "Open in Browser",
+            Redeploy => "Redeploy",
+            Cancel => "Kill",
+            CopyUrl => "Copy URL",
+            CopyId => "Copy ID",
+            CopyCommitMsg => "Copy Commit Msg",
+            CopyLogLines => "Copy Log Lines",
+            _ => self.title(),
+        }
+    }
+}
+
+/// Subsequence match of `query` in `text`, ignoring case. Higher is better: consecutive
+/// characters and matches at word starts score extra, gaps cost a little.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut last: Option<usize> = None;
+    for q in query.chars().flat_map(char::to_lowercase) {
+        if q == ' ' {
+            continue;
+        }
+        let found = pos + text[pos..].iter().position(|&c| c == q)?;
+        score += 1;
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 8;
+        }
+        match last {
+            Some(l) if found == l + 1 => score += 5,
+            Some(l) => score -= (found - l - 1).min(5) as i32,
+            None => score -= found.min(5) as i32,
+        }
+        last = Some(found);
+        pos = found + 1;
+    }
+    Some(score)
+}
+
+/// Palette entries for `query`: available actions, recently used ones first, then the
+/// rest by match quality
+pub fn palette_matches(app: &App, query: &str) -> Vec<Action> {
+    let mut scored: Vec<(Action, i32)> = Action::all()
+        .iter()
+        .copied()
+        .filter(|a| a.in_palette() && a.available(app))
+        .filter_map(|a| {
+            let score = fuzzy_score(query, a.title()).or_else(|| {
+                app.keymap
+                    .keys_for(a)
+                    .contains(&query.trim().to_string())
+                    .then_some(0)
+            })?;
+            Some((a, score))
+        })
+        .collect();
+    let recent_rank = |a: &Action| {
+        app.recent_actions
+            .iter()
+            .position(|r| r == a)
+            .unwrap_or(usize::MAX)
+    };
+    // Stable, so equal scores keep the declaration order
+    scored.sort_by(|(a, sa), (b, sb)| recent_rank(a).cmp(&recent_rank(b)).then(sb.cmp(sa)));
+    scored.into_iter().map(|(a, _)| a).collect()
+}
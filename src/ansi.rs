@@ -0,0 +1,281 @@
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::theme::ThemeColors;
+
+/// Color from an SGR sequence, resolved against the theme at draw time
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AnsiColor {
+    /// 0-7 normal, 8-15 bright
+    Basic(u8),
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl AnsiColor {
+    /// Basic colors follow the theme palette so logs match the rest of the UI;
+    /// 256-color and truecolor values are passed through as-is.
+    fn resolve(&self, colors: &ThemeColors) -> Color {
+        match self {
+            AnsiColor::Basic(n) => match n % 8 {
+                0 => colors.text_dim,           // Black
+                1 => colors.status_error,       // Red
+                2 => colors.status_success,     // Green
+                3 => colors.status_building,    // Yellow
+                4..=6 => colors.accent_primary, // Blue, Magenta, Cyan
+                _ => colors.text_primary,       // White
+            },
+            AnsiColor::Indexed(n) => Color::Indexed(*n),
+            AnsiColor::Rgb(r, g, b) => Color::Rgb(*r, *g, *b),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub struct AnsiStyle {
+    pub fg: Option<AnsiColor>,
+    pub bg: Option<AnsiColor>,
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub reverse: bool,
+}
+
+impl AnsiStyle {
+    pub fn to_style(self, colors: &ThemeColors) -> Style {
+        let mut style = Style::default().fg(colors.text_primary);
+        if let Some(fg) = self.fg {
+            style = style.fg(fg.resolve(colors));
+        }
+        if let Some(bg) = self.bg {
+            style = style.bg(bg.resolve(colors));
+        }
+        for (on, modifier) in [
+            (self.bold, Modifier::BOLD),
+            (self.dim, Modifier::DIM),
+            (self.italic, Modifier::ITALIC),
+            (self.underline, Modifier::UNDERLINED),
+            (self.reverse, Modifier::REVERSED),
+        ] {
+            if on {
+                style = style.add_modifier(modifier);
+            }
+        }
+        style
+    }
+
+    /// Applies the parameters of one SGR (`ESC [ ... m`) sequence
+    fn apply(&mut self, params: &str) {
+        let codes: Vec<u16> = params
+            .split([';', ':'])
+            .map(|p| p.parse().unwrap_or(0))
+            .collect();
+
+        let mut i = 0;
+        while i < codes.len() {
+            match codes[i] {
+                0 => *self = AnsiStyle::default(),
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                7 => self.reverse = true,
+                22 => {
+                    self.bold = false;
+                    self.dim = false;
+                }
+                23 => self.italic = false,
+                24 => self.underline = false,
+                27 => self.reverse = false,
+                c @ 30..=37 => self.fg = Some(AnsiColor::Basic((c - 30) as u8)),
+                39 => self.fg = None,
+                c @ 40..=47 => self.bg = Some(AnsiColor::Basic((c - 40) as u8)),
+                49 => self.bg = None,
+                c @ 90..=97 => self.fg = Some(AnsiColor::Basic((c - 90 + 8) as u8)),
+                c @ 100..=107 => self.bg = Some(AnsiColor::Basic((c - 100 + 8) as u8)),
+                c @ (38 | 48) => {
+                    // Extended colors: 38;5;n or 38;2;r;g;b
+                    let arg = |k: usize| codes.get(i + k).map(|v| (*v).min(255) as u8);
+                    let color = match codes.get(i + 1) {
+                        Some(5) => {
+                            let color = arg(2).map(AnsiColor::Indexed);
+                            i += 2;
+                            color
+                        }
+                        Some(2) => {
+                            let color = match (arg(2), arg(3), arg(4)) {
+                                (Some(r), Some(g), Some(b)) => Some(AnsiColor::Rgb(r, g, b)),
+                                _ => None,
+                            };
+                            i += 4;
+                            color
+                        }
+                        _ => None,
+                    };
+                    if c == 38 {
+                        self.fg = color;
+                    } else {
+                        self.bg = color;
+                    }
+                }
+                _ => {} // Blink, conceal, fonts, ... are ignored
+            }
+            i += 1;
+        }
+    }
+}
+
+/// A styled byte range of the plain text returned by `parse`
+#[derive(Clone, Debug)]
+pub struct AnsiSpan {
+    pub start: usize,
+    pub end: usize,
+    pub style: AnsiStyle,
+}
+
+/// Splits raw terminal output into plain text and SGR style runs.
+/// Every other escape sequence (cursor movement, OSC titles, ...) and control
+/// characters such as `\r` are dropped. Returns no spans for uncolored text.
+pub fn parse(raw: &str) -> (String, Vec<AnsiSpan>) {
+    let mut text = String::with_capacity(raw.len());
+    let mut spans: Vec<AnsiSpan> = Vec::new();
+    let mut style = AnsiStyle::default();
+    let mut run_start = 0;
+
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => match chars.next() {
+                // CSI: parameters, then a final byte in 0x40..=0x7E
+                Some('[') => {
+                    let mut params = String::new();
+                    let mut final_byte = None;
+                    for p in chars.by_ref() {
+                        if ('\x40'..='\x7e').contains(&p) {
+                            final_byte = Some(p);
+                            break;
+                        }
+                        params.push(p);
+                    }
+                    if final_byte == Some('m') {
+                        let mut next = style;
+                        next.apply(&params);
+                        if next != style {
+                            push_run(&mut spans, run_start, text.len(), style);
+                            run_start = text.len();
+                            style = next;
+                        }
+                    }
+                }
+                // OSC: terminated by BEL or ST (ESC \)
+                Some(']') => {
+                    while let Some(p) = chars.next() {
+                        if p == '\x07' {
+                            break;
+                        }
+                        if p == '\x1b' {
+                            chars.next_if_eq(&'\\');
+                            break;
+                        }
+                    }
+                }
+                _ => {} // Two-byte escape (or a dangling ESC at the end)
+            },
+            '\t' | '\n' => text.push(c),
+            c if c.is_control() => {}
+            c => text.push(c),
+        }
+    }
+    push_run(&mut spans, run_start, text.len(), style);
+
+    (text, spans)
+}
+
+/// Records a styled run, skipping empty and unstyled ones
+fn push_run(spans: &mut Vec<AnsiSpan>, start: usize, end: usize, style: AnsiStyle) {
+    if end > start && style != AnsiStyle::default() {
+        spans.push(AnsiSpan { start, end, style });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn styles(raw: &str) -> (String, Vec<(usize, usize, AnsiStyle)>) {
+        let (text, spans) = parse(raw);
+        let spans = spans
+            .into_iter()
+            .map(|s| (s.start, s.end, s.style))
+            .collect();
+        (text, spans)
+    }
+
+    #[test]
+    fn plain_text_has_no_spans() {
+        assert_eq!(styles("hello\tworld"), ("hello\tworld".to_string(), vec![]));
+    }
+
+    #[test]
+    fn basic_colors_and_reset() {
+        let red = AnsiStyle {
+            fg: Some(AnsiColor::Basic(1)),
+            ..Default::default()
+        };
+        assert_eq!(
+            styles("a\x1b[31mred\x1b[0mb"),
+            ("aredb".to_string(), vec![(1, 4, red)])
+        );
+    }
+
+    #[test]
+    fn bright_colors_and_modifiers() {
+        let (_, spans) = styles("\x1b[1;92;104mx");
+        assert_eq!(
+            spans[0].2,
+            AnsiStyle {
+                fg: Some(AnsiColor::Basic(10)),
+                bg: Some(AnsiColor::Basic(12)),
+                bold: true,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn extended_colors() {
+        let (_, spans) = styles("\x1b[38;5;208;48;2;1;2;3mx");
+        assert_eq!(spans[0].2.fg, Some(AnsiColor::Indexed(208)));
+        assert_eq!(spans[0].2.bg, Some(AnsiColor::Rgb(1, 2, 3)));
+
+        // Colon-separated form, and a truncated sequence leaves no color
+        let (_, spans) = styles("\x1b[38:5:42mx\x1b[38;2;1mx");
+        assert_eq!(spans[0].2.fg, Some(AnsiColor::Indexed(42)));
+        assert_eq!(spans.len(), 1);
+    }
+
+    #[test]
+    fn attributes_turn_off_individually() {
+        let (text, spans) = styles("\x1b[1;4ma\x1b[22mb\x1b[24mc");
+        assert_eq!(text, "abc");
+        assert_eq!(spans.len(), 2);
+        assert!(spans[0].2.bold && spans[0].2.underline);
+        assert!(!spans[1].2.bold && spans[1].2.underline);
+        assert_eq!((spans[1].0, spans[1].1), (1, 2));
+    }
+
+    #[test]
+    fn empty_params_reset() {
+        let (_, spans) = styles("\x1b[31ma\x1b[mb");
+        assert_eq!(spans.len(), 1);
+        assert_eq!((spans[0].0, spans[0].1), (0, 1));
+    }
+
+    #[test]
+    fn drops_other_escapes_and_control_characters() {
+        assert_eq!(
+            styles("\x1b]0;title\x07a\x1b[2Kb\r\x1b]8;;url\x1b\\c\x1b7d").0,
+            "abcd"
+        );
+    }
+}
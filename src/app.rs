@@ -1,8 +1,23 @@
+use crate::actions::{Action, HelpSection, Scope};
+use crate::compare::Comparison;
 use crate::config::Config;
+use crate::filter::{FilterError, FilterQuery};
+use crate::hyperlink::LinkRegion;
+use crate::keymap::Keymap;
+use crate::logs::{
+    file_refs, FailureSummary, FileRef, LogLevel, LogLine, LogPhase, LogRow, LogSection,
+    LogTimeMode,
+};
 pub use crate::network::Deployment;
+pub use crate::period::StatPeriod;
 use ratatui::widgets::ListState;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 
-use crate::network::Project;
+use crate::network::{BatchAction, Project, Status};
+
+/// Number of recently run palette commands that are remembered
+const RECENT_ACTIONS: usize = 5;
 
 /// Application State
 pub struct App {
@@ -29,14 +44,55 @@ pub struct App {
 
     // --- Filtering ---
     pub filter_query: String,
+    /// First problem in `filter_query`, highlighted in the filter bar
+    pub filter_error: Option<FilterError>,
     pub is_filter_mode: bool,
     pub filtered_deployments: Vec<Deployment>,
 
+    // --- List Order & Grouping ---
+    pub sort_mode: SortMode,
+    /// Show the list as a project → branch tree
+    pub group_tree: bool,
+    /// Keys of folded groups; kept across refreshes
+    pub collapsed_groups: HashSet<String>,
+    pub deployment_groups: Vec<DeploymentGroup>,
+    /// What the deployment list draws, in order
+    pub list_rows: Vec<ListRow>,
+
     // --- Logs State ---
-    pub logs: Vec<String>,
+    pub logs: Vec<LogLine>,
+    pub native_log_colors: bool,
+    pub hyperlinks: bool,
+    /// URLs drawn in the logs during the last frame, for click hit testing
+    pub log_links: Vec<LinkRegion>,
+    /// Local checkout per project name, for opening file paths from logs
+    pub checkouts: HashMap<String, PathBuf>,
+    pub log_time_mode: LogTimeMode,
+    pub log_sections: Vec<LogSection>,
+    pub failure_summary: Option<FailureSummary>,
+    /// What is actually drawn; `log_list_state` indexes into this
+    pub log_rows: Vec<LogRow>,
+    pub log_problems_only: bool,
+    pub hide_install_noise: bool,
+    pub collapsed_phases: HashSet<LogPhase>,
+    /// Keep the newest line selected as chunks arrive
+    pub log_follow: bool,
+    /// Line where a `V` range selection in the logs started
+    pub log_range_anchor: Option<usize>,
+    /// Lines received while follow mode was paused
+    pub log_new_lines: usize,
     pub is_loading_logs: bool,
     pub error_message: Option<String>,
     pub log_list_state: ListState,
+    pub is_log_search_mode: bool,
+    pub log_search_query: String,
+    pub log_search_regex: Option<regex::Regex>,
+    pub log_search_error: Option<String>,
+    /// Indices into `log_rows` of lines matching the search
+    pub log_search_hits: Vec<usize>,
+    pub log_search_hit_index: Option<usize>,
+    pub is_log_jump_mode: bool,
+    pub log_jump_input: String,
 
     // --- UI State ---
     pub active_pane: ActivePane,
@@ -44,7 +100,16 @@ pub struct App {
     pub enable_mouse: bool,
     pub deployments_area: ratatui::layout::Rect,
     pub logs_area: ratatui::layout::Rect,
+    /// Rows of the deployment list (excluding borders/search bar), for click hit testing
+    pub deployments_list_area: ratatui::layout::Rect,
+    /// (top y, height, row index) of each list row drawn in the last frame
+    pub list_row_spans: Vec<(u16, u16, usize)>,
+    /// Set by the renderer when the terminal is below the full layout size
+    pub is_compact: bool,
+    pub show_log_fullscreen: bool,
     pub is_transparent: bool,
+    pub layouts: Vec<crate::layout::PaneLayout>,
+    pub layout_index: usize,
     pub current_time: String,
     pub scroll_offset: usize,
     pub last_click: Option<(std::time::Instant, u16, u16)>, // Time, x, y
@@ -55,12 +120,77 @@ pub struct App {
     pub total_builds: usize,
     pub active_builds: usize,
     pub error_count: usize,
+    pub mttr_s: Option<u64>,
+    pub failure_streak: usize,
+    pub broken_since: Option<u64>,
+    pub production_broken_since: Option<u64>,
+    pub production_alert_minutes: u64,
+    /// Share of commits in the period that both failed and succeeded
+    pub flaky_rate: u8,
+    /// (project, commit sha) pairs with both failed and successful builds
+    pub flaky_commits: std::collections::HashSet<(String, String)>,
     // pub daily_builds: usize, // Removed
     pub stat_period: StatPeriod,
+    pub is_period_input_mode: bool,
+    pub period_input: String,
+    pub period_input_error: Option<String>,
+
+    // --- Breakdown View ---
+    pub view_mode: ViewMode,
+    pub breakdown_group: BreakdownGroup,
+    pub breakdown_sort: BreakdownColumn,
+    pub breakdown_sort_desc: bool,
+    pub breakdown_rows: Vec<BreakdownRow>,
+    pub breakdown_table_state: ratatui::widgets::TableState,
+
+    // --- Dashboard View ---
+    pub dashboard_sort: DashboardColumn,
+    pub dashboard_sort_desc: bool,
+    pub dashboard_rows: Vec<ProjectHealth>,
+    pub dashboard_table_state: ratatui::widgets::TableState,
+    /// Deployments fetched per project ID for the dashboard, kept apart from the
+    /// current project's list
+    pub dashboard_deployments: HashMap<String, Vec<Deployment>>,
+    /// Period the dashboard history was last fetched for
+    pub dashboard_period: Option<StatPeriod>,
+
+    // --- Branch Matrix View ---
+    pub branch_rows: Vec<BranchRow>,
+    pub branch_table_state: ratatui::widgets::TableState,
+
+    // --- Compare View ---
+    /// Deployment picked with the first `C`, compared with the selection on the second
+    pub compare_base: Option<String>,
+    pub comparison: Option<Comparison>,
+    pub compare_scroll: u16,
+
+    // --- Keys ---
+    pub keymap: Keymap,
+
+    // --- Help Overlay ---
+    pub show_help: bool,
+    pub help_scroll: u16,
+    /// Sections for where help was opened, highlighted in the overlay
+    pub help_sections: Vec<HelpSection>,
+    /// Scroll to the first highlighted section on the next draw
+    pub help_scroll_to_active: bool,
+
+    // --- Command Palette ---
+    pub show_command_palette: bool,
+    pub palette_query: String,
+    /// Available actions matching the query, in display order
+    pub palette_matches: Vec<Action>,
+    pub palette_list_state: ListState,
+    /// Commands last run from the palette, most recent first
+    pub recent_actions: Vec<Action>,
 
     // --- Actions State ---
     pub confirmation_mode: ConfirmationState,
     pub context_menu: Option<ContextMenu>,
+    /// IDs of deployments marked for a batch action
+    pub marked: HashSet<String>,
+    /// Deployment where the `V` range selection in the list started
+    pub mark_anchor: Option<String>,
 
     // --- Regex for Logs ---
     pub log_regex: regex::Regex,
@@ -73,59 +203,213 @@ pub enum ActivePane {
     Logs,
 }
 
-#[derive(PartialEq)]
-pub enum ConfirmationState {
-    None,
-    RedeployPending(String, std::time::Instant), // ID, Time started
-    CancelPending(String, std::time::Instant),
+#[derive(Clone, Copy, PartialEq)]
+pub enum ViewMode {
+    Deployments,
+    Breakdown,
+    Dashboard,
+    Branches,
+    Compare,
 }
 
-pub struct ContextMenu {
-    pub position: (u16, u16),
-    pub deployment_id: String,
-    pub selected_index: usize,
-    pub options: Vec<String>,
+/// Dimension used to group deployments in the breakdown view
+#[derive(Clone, Copy, PartialEq)]
+pub enum BreakdownGroup {
+    Branch,
+    Creator,
+    Target,
+}
+
+impl BreakdownGroup {
+    pub fn next(&self) -> Self {
+        match self {
+            BreakdownGroup::Branch => BreakdownGroup::Creator,
+            BreakdownGroup::Creator => BreakdownGroup::Target,
+            BreakdownGroup::Target => BreakdownGroup::Branch,
+        }
+    }
+
+    pub fn display_text(&self) -> &str {
+        match self {
+            BreakdownGroup::Branch => "Branch",
+            BreakdownGroup::Creator => "Author",
+            BreakdownGroup::Target => "Target",
+        }
+    }
+
+    fn key_of<'a>(&self, d: &'a Deployment) -> &'a str {
+        match self {
+            BreakdownGroup::Branch => &d.branch,
+            BreakdownGroup::Creator => &d.creator,
+            BreakdownGroup::Target => &d.target,
+        }
+    }
 }
 
+/// Order of the deployment list
 #[derive(Clone, Copy, PartialEq)]
-pub enum StatPeriod {
-    Last24h,
-    Last7d,
-    Last30d,
-    All,
+pub enum SortMode {
+    Newest,
+    Duration,
+    Status,
+    Project,
+    Branch,
 }
 
-impl StatPeriod {
+impl SortMode {
     pub fn next(&self) -> Self {
         match self {
-            StatPeriod::Last24h => StatPeriod::Last7d,
-            StatPeriod::Last7d => StatPeriod::Last30d,
-            StatPeriod::Last30d => StatPeriod::All,
-            StatPeriod::All => StatPeriod::Last24h,
+            SortMode::Newest => SortMode::Duration,
+            SortMode::Duration => SortMode::Status,
+            SortMode::Status => SortMode::Project,
+            SortMode::Project => SortMode::Branch,
+            SortMode::Branch => SortMode::Newest,
         }
     }
 
     pub fn display_text(&self) -> &str {
         match self {
-            StatPeriod::Last24h => "Last 24h",
-            StatPeriod::Last7d => "Last 7d",
-            StatPeriod::Last30d => "Last 30d",
-            StatPeriod::All => "All Time",
+            SortMode::Newest => "Newest",
+            SortMode::Duration => "Duration",
+            SortMode::Status => "Status",
+            SortMode::Project => "Project",
+            SortMode::Branch => "Branch",
         }
     }
 }
 
+/// One line of the deployment list: a group header or a deployment.
+/// `_list_state` indexes into `App::list_rows`.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ListRow {
+    /// Index into `App::deployment_groups`
+    Group(usize),
+    /// Index into `App::filtered_deployments`
+    Deployment(usize),
+}
+
+/// A project or branch header of the grouped list
+pub struct DeploymentGroup {
+    /// Stable key, used to remember collapsed groups across refreshes
+    pub key: String,
+    pub project: String,
+    /// Set for branch groups, which nest under their project
+    pub branch: Option<String>,
+    pub builds: usize,
+    pub failed: usize,
+    pub active: usize,
+    /// Status of the newest deployment in the group
+    pub latest: Status,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum BreakdownColumn {
+    Key,
+    Builds,
+    FailureRate,
+    MedianDuration,
+    LastStatus,
+}
+
+impl BreakdownColumn {
+    pub fn all() -> &'static [BreakdownColumn] {
+        &[
+            BreakdownColumn::Key,
+            BreakdownColumn::Builds,
+            BreakdownColumn::FailureRate,
+            BreakdownColumn::MedianDuration,
+            BreakdownColumn::LastStatus,
+        ]
+    }
+}
+
+/// Aggregated metrics for one group in the breakdown view
+pub struct BreakdownRow {
+    pub key: String,
+    pub builds: usize,
+    pub failure_rate: u8,
+    pub median_duration_s: u64,
+    pub last_status: crate::network::Status,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum DashboardColumn {
+    Project,
+    ProductionStatus,
+    Frequency,
+    FailureRate,
+    MedianDuration,
+    SinceSuccess,
+}
+
+impl DashboardColumn {
+    pub fn all() -> &'static [DashboardColumn] {
+        &[
+            DashboardColumn::Project,
+            DashboardColumn::ProductionStatus,
+            DashboardColumn::Frequency,
+            DashboardColumn::FailureRate,
+            DashboardColumn::MedianDuration,
+            DashboardColumn::SinceSuccess,
+        ]
+    }
+}
+
+/// Page of the git provider opened for the selected deployment
+#[derive(Clone, Copy, PartialEq)]
+pub enum GitLink {
+    Commit,
+    Branch,
+    PullRequest,
+}
+
+/// How many past statuses the branch matrix shows per branch
+pub const BRANCH_HISTORY_DOTS: usize = 8;
+
+/// Latest state of one branch in the branch matrix view
+pub struct BranchRow {
+    /// Newest deployment of the branch
+    pub latest: Deployment,
+    pub builds: usize,
+    /// Statuses of the most recent deployments, newest first
+    pub recent: Vec<Status>,
+}
+
+/// Org-wide health metrics for one project in the dashboard view
+pub struct ProjectHealth {
+    pub name: String,
+    /// False until the project's deployments have been fetched
+    pub loaded: bool,
+    pub latest_production: Option<crate::network::Status>,
+    pub deploys_per_day: f64,
+    pub failure_rate: u8,
+    pub median_duration_s: u64,
+    /// Timestamp of the last Ready production deployment
+    pub last_production_success: Option<u64>,
+}
+
+#[derive(PartialEq)]
+pub enum ConfirmationState {
+    None,
+    RedeployPending(String, std::time::Instant), // ID, Time started
+    CancelPending(String, std::time::Instant),
+    BatchPending(BatchAction, Vec<String>, std::time::Instant), // IDs, Time started
+}
+
+pub struct ContextMenu {
+    pub position: (u16, u16),
+    /// Deployment every entry acts on, whatever is selected when it runs
+    pub deployment_id: String,
+    pub selected_index: usize,
+    pub options: Vec<Action>,
+}
+
 impl App {
     pub fn new() -> App {
         let config = Config::load();
 
         // Parse StatPeriod
-        let stat_period = match config.stat_period.as_str() {
-            "7d" => StatPeriod::Last7d,
-            "30d" => StatPeriod::Last30d,
-            "all" => StatPeriod::All,
-            _ => StatPeriod::Last24h,
-        };
+        let stat_period = StatPeriod::parse(&config.stat_period).unwrap_or(StatPeriod::Last24h);
 
         // Compile Regex once
         // Captures:
@@ -143,6 +427,22 @@ impl App {
         let pattern = r#"(?i)(error|failed|failure|warn|warning|info|ready|success|succeeded|building)|(\b\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}\b)|(\d{2}:\d{2}:\d{2})|(".*?")|(\b[\w\-_]+=[^\s]+)|(\b(GET|POST|PUT|DELETE|PATCH)\b)|(\b[1-5]\d{2}\b)|(\b\d+(?:\.\d+)?(?:ms|s|m|h)\b)|(\b\d+(?:\.\d+)?\s?(?:B|KB|MB|GB)\b)|(\b/?[\w\-_.]+(?:/[\w\-_.]+)+\b)|(\b[0-9a-f]{7,40}\b)"#;
         let log_regex = regex::Regex::new(pattern).unwrap();
 
+        // Custom layouts replace presets of the same name; empty ones are ignored
+        let mut layouts = crate::layout::PaneLayout::presets();
+        for custom in config.custom_layouts {
+            if custom.primary.is_empty() && custom.secondary.is_empty() {
+                continue;
+            }
+            match layouts.iter_mut().find(|l| l.name == custom.name) {
+                Some(existing) => *existing = custom,
+                None => layouts.push(custom),
+            }
+        }
+        let layout_index = layouts
+            .iter()
+            .position(|l| l.name == config.layout)
+            .unwrap_or(0);
+
         App {
             deployments: vec![],
             should_quit: false,
@@ -161,20 +461,58 @@ impl App {
             current_project_id: config.last_project_id,
 
             filter_query: String::new(),
+            filter_error: None,
             is_filter_mode: false,
             filtered_deployments: vec![],
+            sort_mode: SortMode::Newest,
+            group_tree: false,
+            collapsed_groups: HashSet::new(),
+            deployment_groups: vec![],
+            list_rows: vec![],
 
             logs: vec![],
+            native_log_colors: config.native_log_colors,
+            hyperlinks: config.hyperlinks,
+            log_links: vec![],
+            checkouts: config
+                .checkouts
+                .iter()
+                .map(|(name, path)| (name.clone(), expand_home(path)))
+                .collect(),
+            log_time_mode: LogTimeMode::Off,
+            log_sections: vec![],
+            failure_summary: None,
+            log_rows: vec![],
+            log_problems_only: false,
+            hide_install_noise: false,
+            collapsed_phases: HashSet::new(),
+            log_follow: false,
+            log_range_anchor: None,
+            log_new_lines: 0,
             is_loading_logs: false,
             error_message: None,
             log_list_state: ListState::default(),
+            is_log_search_mode: false,
+            log_search_query: String::new(),
+            log_search_regex: None,
+            log_search_error: None,
+            log_search_hits: vec![],
+            log_search_hit_index: None,
+            is_log_jump_mode: false,
+            log_jump_input: String::new(),
             active_pane: ActivePane::Deployments,
             show_legend: false,
             enable_mouse: config.enable_mouse,
             deployments_area: ratatui::layout::Rect::default(),
             logs_area: ratatui::layout::Rect::default(),
+            deployments_list_area: ratatui::layout::Rect::default(),
+            list_row_spans: vec![],
+            is_compact: false,
+            show_log_fullscreen: false,
             last_click: None,
             is_transparent: config.is_transparent,
+            layouts,
+            layout_index,
             current_time: chrono::Local::now().format("%H:%M:%S").to_string(),
             scroll_offset: 0,
             avg_duration_s: 0,
@@ -182,8 +520,51 @@ impl App {
             total_builds: 0,
             active_builds: 0,
             error_count: 0,
+            mttr_s: None,
+            failure_streak: 0,
+            broken_since: None,
+            production_broken_since: None,
+            production_alert_minutes: config.production_alert_minutes,
+            flaky_rate: 0,
+            flaky_commits: std::collections::HashSet::new(),
             stat_period,
+            is_period_input_mode: false,
+            period_input: String::new(),
+            period_input_error: None,
+            view_mode: ViewMode::Deployments,
+            breakdown_group: BreakdownGroup::Branch,
+            breakdown_sort: BreakdownColumn::Builds,
+            breakdown_sort_desc: true,
+            breakdown_rows: vec![],
+            breakdown_table_state: ratatui::widgets::TableState::default(),
+            dashboard_sort: DashboardColumn::FailureRate,
+            dashboard_sort_desc: true,
+            dashboard_rows: vec![],
+            dashboard_table_state: ratatui::widgets::TableState::default(),
+            dashboard_deployments: HashMap::new(),
+            dashboard_period: None,
+            branch_rows: vec![],
+            branch_table_state: ratatui::widgets::TableState::default(),
+            compare_base: None,
+            comparison: None,
+            compare_scroll: 0,
+            keymap: Keymap::new(&config.keymap),
+            show_help: false,
+            help_scroll: 0,
+            help_sections: vec![],
+            help_scroll_to_active: false,
+            show_command_palette: false,
+            palette_query: String::new(),
+            palette_matches: vec![],
+            palette_list_state: ListState::default(),
+            recent_actions: config
+                .recent_commands
+                .iter()
+                .filter_map(|id| Action::from_id(id))
+                .collect(),
             confirmation_mode: ConfirmationState::None,
+            marked: HashSet::new(),
+            mark_anchor: None,
             context_menu: None,
             log_regex,
             toast_message: None,
@@ -196,12 +577,14 @@ impl App {
         config.is_transparent = self.is_transparent;
         config.last_project_id = self.current_project_id.clone();
         config.enable_mouse = self.enable_mouse;
-        config.stat_period = match self.stat_period {
-            StatPeriod::Last24h => "24h".to_string(),
-            StatPeriod::Last7d => "7d".to_string(),
-            StatPeriod::Last30d => "30d".to_string(),
-            StatPeriod::All => "all".to_string(),
-        };
+        config.stat_period = self.stat_period.config_string();
+        config.layout = self.layout().name.clone();
+        config.native_log_colors = self.native_log_colors;
+        config.recent_commands = self
+            .recent_actions
+            .iter()
+            .map(|a| a.id().to_string())
+            .collect();
 
         if self.current_project != "All Projects" {
             config.last_project_name = Some(self.current_project.clone());
@@ -211,6 +594,19 @@ impl App {
         config.save();
     }
 
+    pub fn layout(&self) -> &crate::layout::PaneLayout {
+        &self.layouts[self.layout_index]
+    }
+
+    pub fn cycle_layout(&mut self) {
+        self.layout_index = (self.layout_index + 1) % self.layouts.len();
+        // Don't leave focus on a pane that is no longer visible
+        if !self.layout().contains(crate::layout::Pane::Logs) {
+            self.active_pane = ActivePane::Deployments;
+        }
+        self.save_config();
+    }
+
     pub fn on_tick(&mut self) {
         self.spinner_frame = self.spinner_frame.wrapping_add(1);
         self.current_time = chrono::Local::now().format("%H:%M:%S").to_string();
@@ -222,32 +618,55 @@ impl App {
             return;
         }
 
-        let now = chrono::Utc::now().timestamp_millis() as u64;
-        let period_ms = match self.stat_period {
-            StatPeriod::Last24h => 24 * 60 * 60 * 1000,
-            StatPeriod::Last7d => 7 * 24 * 60 * 60 * 1000,
-            StatPeriod::Last30d => 30 * 24 * 60 * 60 * 1000,
-            StatPeriod::All => 0, // Unused
+        let bounds = self.stat_period.bounds();
+
+        // Contextual Stats: Filter by Project of the Selected Deployment (or group header)
+        let selected_row = self._list_state.selected().unwrap_or(0);
+        let (target_project_name, target_branch) = match self.list_rows.get(selected_row) {
+            Some(ListRow::Group(g)) => {
+                let group = &self.deployment_groups[*g];
+                (Some(group.project.clone()), group.branch.clone())
+            }
+            Some(ListRow::Deployment(i)) => {
+                let d = &self.filtered_deployments[*i];
+                (Some(d.name.clone()), Some(d.branch.clone()))
+            }
+            None => (None, None),
         };
 
-        // Contextual Stats: Filter by Project of the Selected Deployment
-        let selected_idx = self._list_state.selected().unwrap_or(0);
-        // Map selection index to the FILTERED list
-        let target_project_name = self
-            .filtered_deployments
-            .get(selected_idx)
-            .map(|d| d.name.clone());
+        // Recovery metrics look past the period so streaks that started earlier still count
+        let project_history: Vec<&crate::network::Deployment> = self
+            .deployments
+            .iter()
+            .filter(|d| {
+                target_project_name
+                    .as_ref()
+                    .is_none_or(|name| &d.name == name)
+            })
+            .collect();
+        let branch_history: Vec<&crate::network::Deployment> = project_history
+            .iter()
+            .filter(|d| target_branch.as_ref().is_none_or(|b| &d.branch == b))
+            .copied()
+            .collect();
+        let production_history: Vec<&crate::network::Deployment> = project_history
+            .iter()
+            .filter(|d| d.target == "production")
+            .copied()
+            .collect();
+
+        let branch_recovery = recovery_stats(&branch_history, bounds);
+        self.mttr_s = branch_recovery.mttr_s;
+        self.failure_streak = branch_recovery.streak;
+        self.broken_since = branch_recovery.broken_since;
+        self.production_broken_since = recovery_stats(&production_history, bounds).broken_since;
 
         // Filter valid Project deployments from the FULL list to show Project-level health metrics.
         let filtered_deployments: Vec<&crate::network::Deployment> = self
             .deployments
             .iter()
             .filter(|d| {
-                let in_time = if self.stat_period == StatPeriod::All {
-                    true
-                } else {
-                    now.saturating_sub(d.timestamp) < period_ms
-                };
+                let in_time = StatPeriod::contains(bounds, d.timestamp);
                 let is_target = match &target_project_name {
                     Some(name) => &d.name == name,
                     None => true,
@@ -299,72 +718,1446 @@ impl App {
         } else {
             self.avg_duration_s = 0;
         }
+
+        // Flakiness: distinct commits in the period that were flagged as flaky
+        let mut commits: Vec<(&str, &str)> = filtered_deployments
+            .iter()
+            .filter_map(|d| Some((d.name.as_str(), d.commit_sha.as_deref()?)))
+            .collect();
+        commits.sort_unstable();
+        commits.dedup();
+        let flaky = commits
+            .iter()
+            .filter(|(name, sha)| {
+                self.flaky_commits
+                    .contains(&(name.to_string(), sha.to_string()))
+            })
+            .count();
+        self.flaky_rate = if commits.is_empty() {
+            0
+        } else {
+            ((flaky as f64 / commits.len() as f64) * 100.0) as u8
+        };
+    }
+
+    /// Flags commits that have both a failed and a successful build in the loaded history.
+    pub fn update_flaky_commits(&mut self) {
+        let mut failed = std::collections::HashSet::new();
+        let mut passed = std::collections::HashSet::new();
+        for d in &self.deployments {
+            let Some(sha) = &d.commit_sha else { continue };
+            match d.status {
+                crate::network::Status::Error => {
+                    failed.insert((d.name.clone(), sha.clone()));
+                }
+                crate::network::Status::Ready => {
+                    passed.insert((d.name.clone(), sha.clone()));
+                }
+                _ => {}
+            }
+        }
+        self.flaky_commits = failed.intersection(&passed).cloned().collect();
+    }
+
+    pub fn is_flaky(&self, d: &Deployment) -> bool {
+        d.commit_sha
+            .as_ref()
+            .is_some_and(|sha| self.flaky_commits.contains(&(d.name.clone(), sha.clone())))
+    }
+
+    /// True when production has been failing for longer than the configured threshold.
+    pub fn production_alert(&self) -> Option<u64> {
+        let since = self.production_broken_since?;
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        let broken_for = now.saturating_sub(since);
+        (broken_for > self.production_alert_minutes * 60 * 1000).then_some(broken_for / 1000)
     }
 
     fn reset_stats(&mut self) {
+        self.mttr_s = None;
+        self.failure_streak = 0;
+        self.broken_since = None;
+        self.production_broken_since = None;
         self.total_builds = 0;
         self.avg_duration_s = 0;
         self.success_rate = 0;
         self.active_builds = 0;
         self.error_count = 0;
+        self.flaky_rate = 0;
     }
 
     pub fn update_filter(&mut self) {
-        let now = chrono::Utc::now().timestamp_millis() as u64;
-        let period_ms = match self.stat_period {
-            StatPeriod::Last24h => 24 * 60 * 60 * 1000,
-            StatPeriod::Last7d => 7 * 24 * 60 * 60 * 1000,
-            StatPeriod::Last30d => 30 * 24 * 60 * 60 * 1000,
-            StatPeriod::All => 0,
-        };
+        let bounds = self.stat_period.bounds();
 
-        // Filter by Branch (Query) AND Time (StatPeriod)
+        // Filter by Query AND Time (StatPeriod)
         // Note: Deployment List should respect the Time Range chosen by user.
-
-        let query = self.filter_query.to_lowercase();
-        let has_query = !query.is_empty();
+        // Re-parsed every time so relative terms like `since:2d` stay current
+        let (query, error) = FilterQuery::parse(&self.filter_query);
+        self.filter_error = error;
 
         self.filtered_deployments = self
             .deployments
             .iter()
-            .filter(|d| {
-                let in_time = if self.stat_period == StatPeriod::All {
-                    true
-                } else {
-                    now.saturating_sub(d.timestamp) < period_ms
+            .filter(|d| StatPeriod::contains(bounds, d.timestamp) && query.matches(d))
+            .cloned()
+            .collect();
+        self.rebuild_list_rows();
+
+        self.update_breakdown();
+        self.update_dashboard();
+        self.update_branches();
+    }
+
+    /// One row per (project, branch) of the filtered deployments, most recently
+    /// deployed first.
+    pub fn update_branches(&mut self) {
+        let mut rows: Vec<BranchRow> = Vec::new();
+        // filtered_deployments is newest first, so the first hit per branch is its latest
+        for d in &self.filtered_deployments {
+            match rows
+                .iter_mut()
+                .find(|r| r.latest.name == d.name && r.latest.branch == d.branch)
+            {
+                Some(row) => {
+                    row.builds += 1;
+                    if row.recent.len() < BRANCH_HISTORY_DOTS {
+                        row.recent.push(d.status.clone());
+                    }
+                }
+                None => rows.push(BranchRow {
+                    latest: d.clone(),
+                    builds: 1,
+                    recent: vec![d.status.clone()],
+                }),
+            }
+        }
+        self.branch_rows = rows;
+        clamp_selection(&mut self.branch_table_state, self.branch_rows.len());
+    }
+
+    /// Filter query that shows the deployment history of a branch matrix row
+    pub fn branch_history_query(&self, row: &BranchRow) -> String {
+        if self.current_project_id.is_some() {
+            format!("branch:\"{}\"", row.latest.branch)
+        } else {
+            format!(
+                "project:\"{}\" branch:\"{}\"",
+                row.latest.name, row.latest.branch
+            )
+        }
+    }
+
+    /// Orders the filtered deployments by `sort_mode` and, in tree mode, nests them
+    /// under project and branch headers, skipping folded groups.
+    pub fn rebuild_list_rows(&mut self) {
+        let ds = &self.filtered_deployments;
+        // Stable sorts, so ties stay newest first
+        let mut order: Vec<usize> = (0..ds.len()).collect();
+        match self.sort_mode {
+            SortMode::Newest => {}
+            SortMode::Duration => order.sort_by_key(|&i| std::cmp::Reverse(ds[i].duration_ms)),
+            SortMode::Status => {
+                order.sort_by_key(|&i| std::cmp::Reverse(status_rank(&ds[i].status)))
+            }
+            SortMode::Project => {
+                order.sort_by_key(|&i| ds[i].name.to_lowercase());
+            }
+            SortMode::Branch => {
+                order.sort_by_key(|&i| ds[i].branch.to_lowercase());
+            }
+        }
+
+        let mut groups = Vec::new();
+        let mut rows = Vec::new();
+        if !self.group_tree {
+            rows = order.into_iter().map(ListRow::Deployment).collect();
+        } else {
+            // Projects and their branches, in order of their first deployment
+            type Branches<'a> = Vec<(&'a str, Vec<usize>)>;
+            let mut tree: Vec<(&str, Branches)> = Vec::new();
+            for i in order {
+                let d = &ds[i];
+                let p = match tree.iter().position(|(name, _)| *name == d.name) {
+                    Some(p) => p,
+                    None => {
+                        tree.push((&d.name, vec![]));
+                        tree.len() - 1
+                    }
                 };
-                let matches_query = if has_query {
-                    d.branch.to_lowercase().contains(&query)
-                } else {
-                    true
+                let branches = &mut tree[p].1;
+                match branches.iter_mut().find(|(branch, _)| *branch == d.branch) {
+                    Some((_, items)) => items.push(i),
+                    None => branches.push((&d.branch, vec![i])),
+                }
+            }
+
+            for (project, branches) in &tree {
+                let all: Vec<usize> = branches
+                    .iter()
+                    .flat_map(|(_, items)| items.clone())
+                    .collect();
+                let key = project.to_string();
+                let folded = self.collapsed_groups.contains(&key);
+                rows.push(ListRow::Group(groups.len()));
+                groups.push(group_of(ds, &all, key, project, None));
+                if folded {
+                    continue;
+                }
+                for (branch, items) in branches {
+                    let key = format!("{}\u{1f}{}", project, branch);
+                    let folded = self.collapsed_groups.contains(&key);
+                    rows.push(ListRow::Group(groups.len()));
+                    groups.push(group_of(ds, items, key, project, Some(branch)));
+                    if !folded {
+                        rows.extend(items.iter().map(|&i| ListRow::Deployment(i)));
+                    }
+                }
+            }
+        }
+        self.deployment_groups = groups;
+        self.list_rows = rows;
+    }
+
+    /// Rebuilds the list rows, keeping the selected row (or the header that now hides it)
+    fn relayout_list(&mut self) {
+        let id = self.selected_row_id();
+        self.rebuild_list_rows();
+        self.select_deployment_by_id(id);
+    }
+
+    pub fn cycle_sort_mode(&mut self) {
+        self.sort_mode = self.sort_mode.next();
+        self.relayout_list();
+    }
+
+    pub fn toggle_group_tree(&mut self) {
+        self.group_tree = !self.group_tree;
+        self.relayout_list();
+    }
+
+    /// Folds or unfolds the selected group, or the branch group of the selected deployment
+    pub fn toggle_selected_group(&mut self) {
+        if !self.group_tree {
+            return;
+        }
+        let key = match self.list_rows.get(self._list_state.selected().unwrap_or(0)) {
+            Some(ListRow::Group(g)) => self.deployment_groups[*g].key.clone(),
+            Some(ListRow::Deployment(i)) => {
+                let d = &self.filtered_deployments[*i];
+                format!("{}\u{1f}{}", d.name, d.branch)
+            }
+            None => return,
+        };
+        if !self.collapsed_groups.remove(&key) {
+            self.collapsed_groups.insert(key.clone());
+        }
+        self.rebuild_list_rows();
+        self.select_deployment_by_id(Some(format!("group:{}", key)));
+    }
+
+    /// Unfolds everything if anything is folded, otherwise folds every project
+    pub fn toggle_all_groups(&mut self) {
+        if !self.group_tree {
+            return;
+        }
+        let id = self.selected_row_id();
+        if self.collapsed_groups.is_empty() {
+            self.collapsed_groups = self
+                .deployment_groups
+                .iter()
+                .filter(|g| g.branch.is_none())
+                .map(|g| g.key.clone())
+                .collect();
+        } else {
+            self.collapsed_groups.clear();
+        }
+        self.rebuild_list_rows();
+        self.select_deployment_by_id(id);
+    }
+
+    /// Groups the filtered deployments by the active breakdown dimension.
+    pub fn update_breakdown(&mut self) {
+        let mut rows: Vec<BreakdownRow> = Vec::new();
+        let mut durations: Vec<Vec<u64>> = Vec::new();
+        let mut failures: Vec<usize> = Vec::new();
+
+        // filtered_deployments is newest first, so the first hit per group is the latest status
+        for d in &self.filtered_deployments {
+            let key = self.breakdown_group.key_of(d);
+            let idx = match rows.iter().position(|r| r.key == key) {
+                Some(idx) => idx,
+                None => {
+                    rows.push(BreakdownRow {
+                        key: key.to_string(),
+                        builds: 0,
+                        failure_rate: 0,
+                        median_duration_s: 0,
+                        last_status: d.status.clone(),
+                    });
+                    durations.push(vec![]);
+                    failures.push(0);
+                    rows.len() - 1
+                }
+            };
+
+            rows[idx].builds += 1;
+            if matches!(d.status, crate::network::Status::Error) {
+                failures[idx] += 1;
+            }
+            if matches!(d.status, crate::network::Status::Ready) {
+                durations[idx].push(d.duration_ms);
+            }
+        }
+
+        for (i, row) in rows.iter_mut().enumerate() {
+            row.failure_rate = ((failures[i] as f64 / row.builds as f64) * 100.0) as u8;
+            row.median_duration_s = median(&mut durations[i]) / 1000;
+        }
+
+        let desc = self.breakdown_sort_desc;
+        rows.sort_by(|a, b| {
+            let ord = match self.breakdown_sort {
+                BreakdownColumn::Key => a.key.to_lowercase().cmp(&b.key.to_lowercase()),
+                BreakdownColumn::Builds => a.builds.cmp(&b.builds),
+                BreakdownColumn::FailureRate => a.failure_rate.cmp(&b.failure_rate),
+                BreakdownColumn::MedianDuration => a.median_duration_s.cmp(&b.median_duration_s),
+                BreakdownColumn::LastStatus => {
+                    status_rank(&a.last_status).cmp(&status_rank(&b.last_status))
+                }
+            };
+            if desc {
+                ord.reverse()
+            } else {
+                ord
+            }
+        });
+
+        self.breakdown_rows = rows;
+
+        // Keep selection in bounds after regrouping
+        let len = self.breakdown_rows.len();
+        match self.breakdown_table_state.selected() {
+            Some(i) if i >= len => {
+                self.breakdown_table_state
+                    .select(if len > 0 { Some(len - 1) } else { None })
+            }
+            None if len > 0 => self.breakdown_table_state.select(Some(0)),
+            _ => {}
+        }
+    }
+
+    /// Sorts the breakdown by `column`, toggling direction if it is already active.
+    pub fn sort_breakdown_by(&mut self, column: BreakdownColumn) {
+        if self.breakdown_sort == column {
+            self.breakdown_sort_desc = !self.breakdown_sort_desc;
+        } else {
+            self.breakdown_sort = column;
+            // Names read best A-Z, metrics read best highest first
+            self.breakdown_sort_desc = column != BreakdownColumn::Key;
+        }
+        self.update_breakdown();
+    }
+
+    /// Switches to a new period and re-applies the list filter, keeping the selection.
+    pub fn set_stat_period(&mut self, period: StatPeriod) {
+        self.stat_period = period;
+        self.save_config();
+
+        let current_id = self.selected_row_id();
+        self.update_filter();
+        self.select_deployment_by_id(current_id);
+    }
+
+    /// Builds one health row per project from the deployments fetched for the dashboard.
+    ///
+    /// Frequency, failure rate and build time use the selected period; the production
+    /// status and last successful production deploy look at every fetched deployment.
+    pub fn update_dashboard(&mut self) {
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        let bounds = self.stat_period.bounds();
+
+        let mut rows: Vec<ProjectHealth> = self
+            .projects
+            .iter()
+            .map(|p| {
+                // Fetched pages are newest first
+                let project: Vec<&Deployment> = self
+                    .dashboard_deployments
+                    .get(&p.id)
+                    .map(|d| d.iter().collect())
+                    .unwrap_or_default();
+                let in_period: Vec<&&Deployment> = project
+                    .iter()
+                    .filter(|d| StatPeriod::contains(bounds, d.timestamp))
+                    .collect();
+                let production: Vec<&&Deployment> = project
+                    .iter()
+                    .filter(|d| d.target == "production")
+                    .collect();
+
+                // Span the rate over the period, or the observed history for All Time
+                let span_start = match bounds {
+                    Some((start, _)) => start,
+                    None => in_period.last().map(|d| d.timestamp).unwrap_or(now),
                 };
-                in_time && matches_query
+                let span_days = (now.saturating_sub(span_start) as f64 / 86_400_000.0).max(1.0);
+
+                let failures = in_period
+                    .iter()
+                    .filter(|d| matches!(d.status, crate::network::Status::Error))
+                    .count();
+                let mut durations: Vec<u64> = in_period
+                    .iter()
+                    .filter(|d| matches!(d.status, crate::network::Status::Ready))
+                    .map(|d| d.duration_ms)
+                    .collect();
+
+                ProjectHealth {
+                    name: p.name.clone(),
+                    loaded: self.dashboard_deployments.contains_key(&p.id),
+                    latest_production: production.first().map(|d| d.status.clone()),
+                    deploys_per_day: in_period.len() as f64 / span_days,
+                    failure_rate: if in_period.is_empty() {
+                        0
+                    } else {
+                        ((failures as f64 / in_period.len() as f64) * 100.0) as u8
+                    },
+                    median_duration_s: median(&mut durations) / 1000,
+                    last_production_success: production
+                        .iter()
+                        .find(|d| matches!(d.status, crate::network::Status::Ready))
+                        .map(|d| d.timestamp),
+                }
             })
-            .cloned()
             .collect();
+
+        let desc = self.dashboard_sort_desc;
+        rows.sort_by(|a, b| {
+            // Projects still loading go last whatever the sort
+            if a.loaded != b.loaded {
+                return b.loaded.cmp(&a.loaded);
+            }
+            let ord = match self.dashboard_sort {
+                DashboardColumn::Project => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                DashboardColumn::ProductionStatus => {
+                    let rank = |s: &Option<crate::network::Status>| s.as_ref().map(status_rank);
+                    rank(&a.latest_production).cmp(&rank(&b.latest_production))
+                }
+                DashboardColumn::Frequency => a.deploys_per_day.total_cmp(&b.deploys_per_day),
+                DashboardColumn::FailureRate => a.failure_rate.cmp(&b.failure_rate),
+                DashboardColumn::MedianDuration => a.median_duration_s.cmp(&b.median_duration_s),
+                DashboardColumn::SinceSuccess => {
+                    // Never succeeded counts as the longest wait
+                    let age = |t: Option<u64>| t.map_or(u64::MAX, |t| now.saturating_sub(t));
+                    age(a.last_production_success).cmp(&age(b.last_production_success))
+                }
+            };
+            if desc {
+                ord.reverse()
+            } else {
+                ord
+            }
+        });
+
+        self.dashboard_rows = rows;
+        clamp_selection(&mut self.dashboard_table_state, self.dashboard_rows.len());
+    }
+
+    /// Sorts the dashboard by `column`, toggling direction if it is already active.
+    pub fn sort_dashboard_by(&mut self, column: DashboardColumn) {
+        if self.dashboard_sort == column {
+            self.dashboard_sort_desc = !self.dashboard_sort_desc;
+        } else {
+            self.dashboard_sort = column;
+            self.dashboard_sort_desc = column != DashboardColumn::Project;
+        }
+        self.update_dashboard();
+    }
+
+    /// Switches to `project` (or all projects), clearing the loaded data.
+    /// The caller is responsible for requesting the new deployments.
+    pub fn switch_project(&mut self, project: Option<Project>) {
+        match project {
+            Some(p) => {
+                self.current_project = p.name;
+                self.current_project_id = Some(p.id);
+                // Force switch to "All" time range
+                self.stat_period = StatPeriod::All;
+            }
+            None => {
+                self.current_project = "All Projects".to_string();
+                self.current_project_id = None;
+            }
+        }
+
+        // CLEAR DATA IMMEDIATELY
+        self.deployments.clear();
+        self.filtered_deployments.clear();
+        self.rebuild_list_rows();
+        self.clear_logs();
+        self._list_state.select(None);
+
+        self.save_config();
+    }
+
+    /// Compiles the log search query (smart case) and jumps to the first hit at or after the cursor.
+    pub fn set_log_search(&mut self, query: String) {
+        self.log_search_query = query;
+        self.log_search_error = None;
+        self.log_search_regex = None;
+
+        if !self.log_search_query.is_empty() {
+            let case_sensitive = self.log_search_query.chars().any(char::is_uppercase);
+            match regex::RegexBuilder::new(&self.log_search_query)
+                .case_insensitive(!case_sensitive)
+                .build()
+            {
+                Ok(re) => self.log_search_regex = Some(re),
+                Err(_) => self.log_search_error = Some("Invalid regex".to_string()),
+            }
+        }
+
+        self.update_log_search();
+
+        // Incremental: move to the nearest hit from the current line
+        let current = self.log_list_state.selected().unwrap_or(0);
+        if let Some(pos) = self.log_search_hits.iter().position(|&l| l >= current) {
+            self.select_log_hit(pos);
+        } else if !self.log_search_hits.is_empty() {
+            self.select_log_hit(0);
+        }
+    }
+
+    /// Recomputes search hits, e.g. after new log lines arrived.
+    pub fn update_log_search(&mut self) {
+        self.log_search_hits = match &self.log_search_regex {
+            Some(re) => self
+                .log_rows
+                .iter()
+                .enumerate()
+                .filter(
+                    |(_, row)| matches!(row, LogRow::Line(i) if re.is_match(&self.logs[*i].text)),
+                )
+                .map(|(pos, _)| pos)
+                .collect(),
+            None => vec![],
+        };
+        if self
+            .log_search_hit_index
+            .is_some_and(|i| i >= self.log_search_hits.len())
+        {
+            self.log_search_hit_index = None;
+        }
+    }
+
+    /// Moves to the next (or previous) hit relative to the selected line, wrapping around.
+    pub fn next_log_hit(&mut self, forward: bool) {
+        if self.log_search_hits.is_empty() {
+            return;
+        }
+        let current = self.log_list_state.selected().unwrap_or(0);
+        let pos = if forward {
+            self.log_search_hits
+                .iter()
+                .position(|&l| l > current)
+                .unwrap_or(0)
+        } else {
+            self.log_search_hits
+                .iter()
+                .rposition(|&l| l < current)
+                .unwrap_or(self.log_search_hits.len() - 1)
+        };
+        self.select_log_hit(pos);
+    }
+
+    fn select_log_hit(&mut self, pos: usize) {
+        self.log_search_hit_index = Some(pos);
+        self.log_list_state.select(Some(self.log_search_hits[pos]));
+    }
+
+    pub fn clear_log_search(&mut self) {
+        self.is_log_search_mode = false;
+        self.log_search_query.clear();
+        self.log_search_regex = None;
+        self.log_search_error = None;
+        self.log_search_hits.clear();
+        self.log_search_hit_index = None;
+    }
+
+    /// Selects a 1-based log line, or the next shown line if it is hidden.
+    pub fn jump_to_log_line(&mut self, line: usize) {
+        if self.log_rows.is_empty() {
+            return;
+        }
+        let target = line.saturating_sub(1);
+        let pos = self
+            .log_rows
+            .iter()
+            .position(|row| matches!(row, LogRow::Line(i) if *i >= target))
+            .unwrap_or(self.log_rows.len() - 1);
+        self.log_list_state.select(Some(pos));
+    }
+
+    pub fn set_logs(&mut self, lines: Vec<LogLine>) {
+        self.logs.clear();
+        self.append_logs(lines);
+        self.log_new_lines = 0;
+    }
+
+    pub fn append_logs(&mut self, lines: Vec<LogLine>) {
+        if !self.log_follow {
+            self.log_new_lines += lines.len();
+        }
+        // Resolve the selection against the old sections before they are replaced
+        let selected = self.selected_log_row();
+        for mut line in lines {
+            line.level = LogLevel::classify(&line.text, &self.log_regex);
+            self.logs.push(line);
+        }
+        self.log_sections = crate::logs::detect_sections(&self.logs);
+        self.failure_summary = crate::logs::find_failure(&self.logs, &self.log_sections);
+        self.rebuild_log_rows_from(selected);
+    }
+
+    pub fn clear_logs(&mut self) {
+        self.set_logs(vec![]);
+        self.log_list_state.select(None);
+    }
+
+    /// Whether line `i` passes the level and install-noise filters
+    fn is_log_line_shown(&self, i: usize, section: Option<&LogSection>) -> bool {
+        let level = self.logs[i].level;
+        if self.log_problems_only && level == LogLevel::Info {
+            return false;
+        }
+        // Install output is mostly progress and deprecation warnings; keep only errors
+        let in_install = section.is_some_and(|s| s.phase == LogPhase::Install);
+        !(self.hide_install_noise && in_install && level < LogLevel::Error)
+    }
+
+    /// Recomputes `log_rows` from the filters and folds, keeping the selected line.
+    pub fn rebuild_log_rows(&mut self) {
+        let selected = self.selected_log_row();
+        self.rebuild_log_rows_from(selected);
+    }
+
+    /// `selected` is the previously selected row and the log line it starts at
+    fn rebuild_log_rows_from(&mut self, selected: Option<(LogRow, usize)>) {
+        let mut rows = Vec::with_capacity(self.logs.len() + self.log_sections.len());
+        let mut line = 0;
+        for (s, section) in self.log_sections.iter().enumerate() {
+            // Lines before the first section
+            for i in line..section.start {
+                if self.is_log_line_shown(i, None) {
+                    rows.push(LogRow::Line(i));
+                }
+            }
+
+            let shown: Vec<usize> = (section.start..section.end)
+                .filter(|&i| self.is_log_line_shown(i, Some(section)))
+                .collect();
+            // Filtered-out sections disappear entirely, folded ones keep their header
+            if !shown.is_empty() || !(self.log_problems_only || self.hide_install_noise) {
+                rows.push(LogRow::Section(s));
+                if !self.collapsed_phases.contains(&section.phase) {
+                    rows.extend(shown.into_iter().map(LogRow::Line));
+                }
+            }
+            line = section.end;
+        }
+        for i in line..self.logs.len() {
+            if self.is_log_line_shown(i, None) {
+                rows.push(LogRow::Line(i));
+            }
+        }
+        self.log_rows = rows;
+
+        // Restore the selection on the same row, or the nearest one after it
+        if let Some((old, anchor)) = selected {
+            let line_of = |row: &LogRow| self.row_line(row).unwrap_or(usize::MAX);
+            let pos = self
+                .log_rows
+                .iter()
+                .position(|r| *r == old && line_of(r) == anchor)
+                .or_else(|| self.log_rows.iter().position(|r| line_of(r) >= anchor))
+                .or(self.log_rows.len().checked_sub(1));
+            self.log_list_state.select(pos);
+        }
+        if self.log_follow && !self.log_rows.is_empty() {
+            self.log_list_state.select(Some(self.log_rows.len() - 1));
+        }
+
+        self.update_log_search();
+    }
+
+    /// Turns follow mode on (jumping to the newest line) or off.
+    pub fn set_log_follow(&mut self, follow: bool) {
+        self.log_follow = follow;
+        self.log_new_lines = 0;
+        if follow && !self.log_rows.is_empty() {
+            self.log_list_state.select(Some(self.log_rows.len() - 1));
+        }
+    }
+
+    /// Pauses follow mode once the selection has moved away from the newest line.
+    pub fn update_log_follow(&mut self) {
+        if self.log_follow
+            && !self.log_rows.is_empty()
+            && self.log_list_state.selected() != Some(self.log_rows.len() - 1)
+        {
+            self.log_follow = false;
+        }
+    }
+
+    /// Reference point for the "since build start" gutter: the deployment's creation time
+    pub fn log_build_start(&self) -> u64 {
+        self.selected_deployment()
+            .map(|d| d.timestamp)
+            .or(self.logs.first().map(|l| l.timestamp))
+            .unwrap_or(0)
+    }
+
+    /// Inclusive range of log lines between the `V` anchor and the cursor
+    pub fn log_range(&self) -> Option<(usize, usize)> {
+        let anchor = self.log_range_anchor?;
+        let cursor = self.selected_log_line()?;
+        Some((anchor.min(cursor), anchor.max(cursor)))
+    }
+
+    /// Text of the shown lines in the range selection, or of the line under the cursor
+    pub fn selected_log_text(&self) -> Option<String> {
+        let (from, to) = self.log_range().or_else(|| {
+            let line = match self.log_rows.get(self.log_list_state.selected()?)? {
+                LogRow::Line(i) => *i,
+                LogRow::Section(_) => return None,
+            };
+            Some((line, line))
+        })?;
+        let lines: Vec<&str> = self
+            .log_rows
+            .iter()
+            .filter_map(|row| match row {
+                LogRow::Line(i) if (from..=to).contains(i) => Some(self.logs[*i].text.as_str()),
+                _ => None,
+            })
+            .collect();
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
+    /// Writes the full (unfiltered) log of the selected deployment to the working directory.
+    pub fn export_logs(&self, with_timestamps: bool) -> Result<std::path::PathBuf, String> {
+        let d = self
+            .selected_deployment()
+            .ok_or_else(|| "No deployment selected".to_string())?;
+        if self.logs.is_empty() {
+            return Err("No logs to save".to_string());
+        }
+
+        let mut content = String::new();
+        for line in &self.logs {
+            if with_timestamps {
+                let time = chrono::DateTime::from_timestamp_millis(line.timestamp as i64)
+                    .unwrap_or_default()
+                    .with_timezone(&chrono::Local);
+                content.push_str(&time.format("%Y-%m-%d %H:%M:%S%.3f ").to_string());
+            }
+            content.push_str(&line.text);
+            content.push('\n');
+        }
+
+        // Never overwrite: earlier exports get a numeric suffix instead
+        let dir = std::env::current_dir().unwrap_or_default();
+        let stem = format!(
+            "polymer-{}-{}{}",
+            d.name,
+            d.short_id,
+            if with_timestamps { "-timestamps" } else { "" }
+        );
+        let mut n = 1;
+        loop {
+            let name = match n {
+                1 => format!("{}.log", stem),
+                _ => format!("{}-{}.log", stem, n),
+            };
+            let path = dir.join(name);
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(mut file) => {
+                    use std::io::Write;
+                    file.write_all(content.as_bytes())
+                        .map_err(|e| format!("Failed to save log: {}", e))?;
+                    return Ok(path);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => n += 1,
+                Err(e) => return Err(format!("Failed to save log: {}", e)),
+            }
+        }
+    }
+
+    /// Copies what a copy `action` names from `target` (default: the selection) and
+    /// reports the result in a toast.
+    pub fn copy_item(&mut self, action: Action, target: Option<&str>) {
+        let deployment = self.target_deployment(target);
+        let text = match (action, deployment) {
+            (Action::CopyLogLines, _) => self.selected_log_text(),
+            (Action::CopyUrl, Some(d)) => Some(format!("https://{}", d.domain)),
+            (Action::CopyId, Some(d)) => Some(d.id.clone()),
+            (Action::CopyCommitMsg, Some(d)) => Some(d.commit_msg.clone()),
+            _ => None,
+        };
+        let colors = self.current_theme.get_colors();
+        let (msg, color) = match text.map(|t| crate::clipboard::copy(&t)) {
+            Some(Ok(())) => {
+                if action == Action::CopyLogLines {
+                    self.log_range_anchor = None;
+                }
+                (
+                    action.menu_title().replacen("Copy", "Copied", 1),
+                    colors.status_success,
+                )
+            }
+            Some(Err(e)) => (e, colors.status_error),
+            None => ("Nothing to copy".to_string(), colors.text_dim),
+        };
+        self.toast_message = Some((msg, color, std::time::Instant::now()));
+    }
+
+    /// The first file mentioned on the selected log line that exists in the project's
+    /// checkout (or in the working directory when no checkout is configured)
+    pub fn selected_file_ref(&self) -> Result<(PathBuf, FileRef), String> {
+        let d = self
+            .selected_deployment()
+            .ok_or_else(|| "No deployment selected".to_string())?;
+        let line = self
+            .selected_log_line()
+            .ok_or_else(|| "No log line selected".to_string())?;
+        let refs = file_refs(&self.logs[line].text);
+        let Some(first) = refs.first() else {
+            return Err("No file path on this line".to_string());
+        };
+
+        let root = match self.checkouts.get(&d.name) {
+            Some(root) => root.clone(),
+            None => std::env::current_dir().map_err(|e| e.to_string())?,
+        };
+        let not_found = if self.checkouts.contains_key(&d.name) {
+            format!("{} not found in {}", first.path, root.display())
+        } else {
+            format!(
+                "{} not found; map a checkout for {} under \"checkouts\" in the config",
+                first.path, d.name
+            )
+        };
+        refs.into_iter()
+            .find_map(|r| {
+                let path = root.join(&r.path);
+                path.is_file().then_some((path, r))
+            })
+            .ok_or(not_found)
+    }
+
+    pub fn selected_deployment(&self) -> Option<&Deployment> {
+        self.deployment_at(self._list_state.selected()?)
+    }
+
+    /// The deployment with ID `target`, or the selection when there is no target
+    pub fn target_deployment(&self, target: Option<&str>) -> Option<&Deployment> {
+        match target {
+            Some(id) => self.deployments.iter().find(|d| d.id == id),
+            None => self.selected_deployment(),
+        }
+    }
+
+    /// Deployment drawn at list row `row`; `None` for group headers
+    pub fn deployment_at(&self, row: usize) -> Option<&Deployment> {
+        match self.list_rows.get(row)? {
+            ListRow::Deployment(i) => self.filtered_deployments.get(*i),
+            ListRow::Group(_) => None,
+        }
+    }
+
+    /// Group header drawn at list row `row`
+    pub fn group_at(&self, row: usize) -> Option<&DeploymentGroup> {
+        match self.list_rows.get(row)? {
+            ListRow::Group(g) => self.deployment_groups.get(*g),
+            ListRow::Deployment(_) => None,
+        }
+    }
+
+    /// Identity of the selected row that survives a rebuild: a deployment ID or
+    /// `group:<key>` for headers
+    pub fn selected_row_id(&self) -> Option<String> {
+        let row = self._list_state.selected()?;
+        match self.list_rows.get(row)? {
+            ListRow::Group(g) => Some(format!("group:{}", self.deployment_groups[*g].key)),
+            ListRow::Deployment(i) => Some(self.filtered_deployments[*i].id.clone()),
+        }
+    }
+
+    pub fn is_selected_building(&self) -> bool {
+        self.selected_deployment()
+            .is_some_and(|d| matches!(d.status, Status::Building | Status::Initializing))
+    }
+
+    /// Selects the first error line, unfolding its section. Returns false if the log has none.
+    pub fn jump_to_first_error(&mut self) -> bool {
+        let Some(summary) = &self.failure_summary else {
+            return false;
+        };
+        let line = summary.line;
+        if let Some(phase) = summary.phase {
+            self.collapsed_phases.remove(&phase);
+        }
+        self.log_follow = false;
+        self.rebuild_log_rows();
+        self.jump_to_log_line(line + 1);
+        true
+    }
+
+    /// Index into `logs` of the selected row (a section header maps to its first line)
+    pub fn selected_log_line(&self) -> Option<usize> {
+        self.selected_log_row().map(|(_, line)| line)
+    }
+
+    fn selected_log_row(&self) -> Option<(LogRow, usize)> {
+        let row = *self.log_rows.get(self.log_list_state.selected()?)?;
+        Some((row, self.row_line(&row)?))
+    }
+
+    /// First log line of a row; `None` for a section that no longer exists
+    fn row_line(&self, row: &LogRow) -> Option<usize> {
+        match row {
+            LogRow::Section(s) => self.log_sections.get(*s).map(|section| section.start),
+            LogRow::Line(i) => Some(*i),
+        }
+    }
+
+    pub fn toggle_log_problems_only(&mut self) {
+        self.log_problems_only = !self.log_problems_only;
+        self.rebuild_log_rows();
+    }
+
+    pub fn toggle_install_noise(&mut self) {
+        self.hide_install_noise = !self.hide_install_noise;
+        self.rebuild_log_rows();
+    }
+
+    /// Folds or unfolds the section containing the selected row.
+    pub fn toggle_log_section(&mut self) {
+        let Some(line) = self.selected_log_line() else {
+            return;
+        };
+        let Some(section) = self
+            .log_sections
+            .iter()
+            .find(|s| line >= s.start && line < s.end)
+        else {
+            return;
+        };
+        let phase = section.phase;
+        if !self.collapsed_phases.remove(&phase) {
+            self.collapsed_phases.insert(phase);
+            // Keep the cursor on the header of the section being folded
+            if let Some(s) = self.log_sections.iter().position(|s| s.phase == phase) {
+                if let Some(pos) = self.log_rows.iter().position(|r| *r == LogRow::Section(s)) {
+                    self.log_list_state.select(Some(pos));
+                }
+            }
+        }
+        self.rebuild_log_rows();
+    }
+
+    /// Folds every section, or unfolds all of them if they are already folded.
+    pub fn toggle_all_log_sections(&mut self) {
+        let all_folded = self
+            .log_sections
+            .iter()
+            .all(|s| self.collapsed_phases.contains(&s.phase));
+        if all_folded {
+            self.collapsed_phases.clear();
+        } else {
+            self.collapsed_phases
+                .extend(self.log_sections.iter().map(|s| s.phase));
+        }
+        self.rebuild_log_rows();
+    }
+
+    /// Maps a screen position to the list row drawn there
+    pub fn list_row_at(&self, x: u16, y: u16) -> Option<usize> {
+        if !self
+            .deployments_list_area
+            .contains(ratatui::layout::Position::new(x, y))
+        {
+            return None;
+        }
+        self.list_row_spans
+            .iter()
+            .find(|(top, height, _)| y >= *top && y < top + height)
+            .map(|(_, _, row)| *row)
     }
 
     pub fn get_selected_deployment_id(&self) -> Option<String> {
-        let idx = self._list_state.selected()?;
-        self.filtered_deployments.get(idx).map(|d| d.id.clone())
+        self.selected_deployment().map(|d| d.id.clone())
+    }
+
+    /// Range of list rows covered by the `V` selection
+    fn mark_range(&self) -> Option<(usize, usize)> {
+        let anchor_id = self.mark_anchor.as_ref()?;
+        let anchor = (0..self.list_rows.len())
+            .position(|row| self.deployment_at(row).is_some_and(|d| &d.id == anchor_id))?;
+        let cursor = self._list_state.selected()?;
+        Some((anchor.min(cursor), anchor.max(cursor)))
+    }
+
+    /// Deployment IDs inside the `V` range
+    fn range_ids(&self) -> HashSet<&str> {
+        match self.mark_range() {
+            Some((from, to)) => (from..=to)
+                .filter_map(|row| self.deployment_at(row))
+                .map(|d| d.id.as_str())
+                .collect(),
+            None => HashSet::new(),
+        }
+    }
+
+    /// Whether the deployment at list row `row` is marked or inside the `V` range
+    pub fn is_marked(&self, row: usize) -> bool {
+        let Some(d) = self.deployment_at(row) else {
+            return false;
+        };
+        self.marked.contains(&d.id)
+            || self
+                .mark_range()
+                .is_some_and(|(from, to)| row >= from && row <= to)
+    }
+
+    /// Number of marked deployments, counting the pending `V` range
+    pub fn mark_count(&self) -> usize {
+        self.batch_targets(BatchAction::Redeploy).len()
+    }
+
+    pub fn toggle_mark(&mut self) {
+        if let Some(id) = self.get_selected_deployment_id() {
+            if !self.marked.remove(&id) {
+                self.marked.insert(id);
+            }
+        }
+    }
+
+    /// Starts a `V` range selection, or adds the current one to the marks
+    pub fn toggle_mark_range(&mut self) {
+        if self.mark_range().is_some() {
+            let ids: Vec<String> = self.range_ids().into_iter().map(str::to_string).collect();
+            self.marked.extend(ids);
+            self.mark_anchor = None;
+        } else {
+            self.mark_anchor = self.get_selected_deployment_id();
+        }
+    }
+
+    /// Marks every queued or running preview build of the selected deployment's branch.
+    /// Returns how many were marked.
+    pub fn mark_queued_branch_builds(&mut self) -> usize {
+        let Some(selected) = self.selected_deployment() else {
+            return 0;
+        };
+        let ids: Vec<String> = self
+            .deployments
+            .iter()
+            .filter(|d| {
+                d.name == selected.name
+                    && d.branch == selected.branch
+                    && d.target != "production"
+                    && matches!(d.status, Status::Building | Status::Initializing)
+            })
+            .map(|d| d.id.clone())
+            .collect();
+        let count = ids.len();
+        self.marked.extend(ids);
+        count
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+        self.mark_anchor = None;
+    }
+
+    /// Marked deployments `action` applies to, newest first. Only queued or running
+    /// builds can be canceled.
+    pub fn batch_targets(&self, action: BatchAction) -> Vec<String> {
+        let in_range = self.range_ids();
+        self.deployments
+            .iter()
+            .filter(|d| self.marked.contains(&d.id) || in_range.contains(d.id.as_str()))
+            .filter(|d| match action {
+                BatchAction::Redeploy => true,
+                BatchAction::Cancel => {
+                    matches!(d.status, Status::Building | Status::Initializing)
+                }
+            })
+            .map(|d| d.id.clone())
+            .collect()
+    }
+
+    /// The first press asks for confirmation; a second press with the same targets
+    /// confirms and returns the IDs to act on
+    pub fn confirm_batch(&mut self, action: BatchAction) -> Option<Vec<String>> {
+        let ids = self.batch_targets(action);
+        if let ConfirmationState::BatchPending(pending, pending_ids, _) = &self.confirmation_mode {
+            if *pending == action && *pending_ids == ids {
+                self.confirmation_mode = ConfirmationState::None;
+                // Succeeded items are unmarked as results come in; failures stay marked
+                self.marked.extend(ids.iter().cloned());
+                self.mark_anchor = None;
+                return Some(ids);
+            }
+        }
+        self.confirmation_mode =
+            ConfirmationState::BatchPending(action, ids, std::time::Instant::now());
+        None
+    }
+
+    /// URL of `link` for the selected deployment on its git provider
+    pub fn selected_git_url(&self, link: GitLink) -> Result<String, String> {
+        let d = self
+            .selected_deployment()
+            .ok_or("No deployment selected".to_string())?;
+        let project_link = self
+            .projects
+            .iter()
+            .find(|p| p.name == d.name)
+            .and_then(|p| p.link.as_ref());
+        let repo_url = d.repo_url(project_link)?;
+        match link {
+            GitLink::Commit => d
+                .commit_url(&repo_url)
+                .ok_or("No commit SHA for this deployment"),
+            GitLink::Branch => d
+                .branch_url(&repo_url)
+                .ok_or("No branch for this deployment"),
+            GitLink::PullRequest => d
+                .pr_url(&repo_url)
+                .ok_or("No pull request for this deployment"),
+        }
+        .map_err(str::to_string)
+    }
+
+    /// `C`: the first press picks the selected deployment as the base, the second opens
+    /// the comparison with the selection (pressing it on the base again drops it).
+    /// With exactly two marked deployments, compares those right away.
+    /// Returns the two IDs whose logs need fetching once the view is open.
+    pub fn start_compare(&mut self) -> Option<(String, String)> {
+        let (a, b) = if self.marked.len() == 2 {
+            // The older deployment is the base
+            let mut marked: Vec<&Deployment> = self
+                .deployments
+                .iter()
+                .filter(|d| self.marked.contains(&d.id))
+                .collect();
+            marked.sort_by_key(|d| d.timestamp);
+            match marked[..] {
+                [base, head] => (base.id.clone(), head.id.clone()),
+                _ => return None,
+            }
+        } else {
+            let selected = self.get_selected_deployment_id()?;
+            match self.compare_base.take() {
+                Some(base) if base != selected => (base, selected),
+                Some(_) => return None,
+                None => {
+                    self.compare_base = Some(selected);
+                    return None;
+                }
+            }
+        };
+        let find = |id: &str| self.deployments.iter().find(|d| d.id == id).cloned();
+        let comparison = Comparison::new(find(&a)?, find(&b)?, &self.deployments);
+        self.comparison = Some(comparison);
+        self.compare_scroll = 0;
+        self.view_mode = ViewMode::Compare;
+        Some((a, b))
+    }
+
+    /// Scopes whose key bindings are live, most specific first
+    pub fn active_scopes(&self) -> Vec<Scope> {
+        if self.show_help
+            || self.context_menu.is_some()
+            || self.show_theme_selector
+            || self.show_project_selector
+            || self.view_mode != ViewMode::Deployments
+        {
+            vec![Scope::Overlay, Scope::Global]
+        } else if self.show_log_fullscreen {
+            vec![Scope::LogViewer, Scope::Logs, Scope::Global]
+        } else if self.active_pane == ActivePane::Logs {
+            vec![Scope::Logs, Scope::Global]
+        } else {
+            vec![Scope::List, Scope::Global]
+        }
+    }
+
+    /// Action bound to `key` in the current context, per the active keymap
+    pub fn action_for_key(&mut self, key: crossterm::event::KeyEvent) -> Option<Action> {
+        let scopes = self.active_scopes();
+        self.keymap.feed(key, &scopes)
+    }
+
+    /// Opens the help overlay with `sections` highlighted as the current context
+    pub fn open_help(&mut self, sections: Vec<HelpSection>) {
+        self.show_help = true;
+        self.help_sections = sections;
+        self.help_scroll = 0;
+        self.help_scroll_to_active = true;
+        self.show_legend = false;
     }
 
+    pub fn open_command_palette(&mut self) {
+        self.show_command_palette = true;
+        self.palette_query.clear();
+        self.update_palette();
+        self.show_legend = false;
+    }
+
+    /// Re-matches the palette query against the actions available right now
+    pub fn update_palette(&mut self) {
+        self.palette_matches = crate::actions::palette_matches(self, &self.palette_query);
+        self.palette_list_state
+            .select((!self.palette_matches.is_empty()).then_some(0));
+    }
+
+    /// Remembers `action` so the palette ranks it first next time
+    pub fn record_recent_action(&mut self, action: Action) {
+        self.recent_actions.retain(|a| *a != action);
+        self.recent_actions.insert(0, action);
+        self.recent_actions.truncate(RECENT_ACTIONS);
+        self.save_config();
+    }
+
+    /// Short ID of a deployment for messages, falling back to the full ID
+    pub fn short_id_of(&self, id: &str) -> String {
+        self.deployments
+            .iter()
+            .find(|d| d.id == id)
+            .map(|d| d.short_id.clone())
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    /// Selects the row for a deployment ID or `group:<key>`. A deployment hidden in a
+    /// folded group selects the header that hides it.
     pub fn select_deployment_by_id(&mut self, id: Option<String>) {
         if let Some(target_id) = id {
-            if let Some(pos) = self
-                .filtered_deployments
-                .iter()
-                .position(|d| d.id == target_id)
-            {
+            let keys = match target_id.strip_prefix("group:") {
+                Some(key) => vec![key.to_string()],
+                None => match self.filtered_deployments.iter().find(|d| d.id == target_id) {
+                    Some(d) => vec![format!("{}\u{1f}{}", d.name, d.branch), d.name.clone()],
+                    None => vec![],
+                },
+            };
+            let pos = (0..self.list_rows.len())
+                .position(|row| self.deployment_at(row).is_some_and(|d| d.id == target_id))
+                .or_else(|| {
+                    keys.iter().find_map(|key| {
+                        (0..self.list_rows.len())
+                            .position(|row| self.group_at(row).is_some_and(|g| &g.key == key))
+                    })
+                });
+            if let Some(pos) = pos {
                 self._list_state.select(Some(pos));
                 return;
             }
         }
         // Fallback: If ID not found (gone or filtered out), select 0 if list not empty
-        if !self.filtered_deployments.is_empty() {
+        if !self.list_rows.is_empty() {
             self._list_state.select(Some(0));
         } else {
             self._list_state.select(None);
         }
     }
 }
+
+/// Median of the given values (0 when empty). Sorts the slice in place.
+pub fn median(values: &mut [u64]) -> u64 {
+    if values.is_empty() {
+        return 0;
+    }
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2
+    } else {
+        values[mid]
+    }
+}
+
+/// Failure/recovery metrics for a deployment history
+pub struct RecoveryStats {
+    /// Mean time from the first failed build to the next Ready build
+    pub mttr_s: Option<u64>,
+    /// Consecutive failed builds since the last Ready build
+    pub streak: usize,
+    /// Timestamp of the first failure in the current streak
+    pub broken_since: Option<u64>,
+}
+
+/// Computes recovery metrics from a newest-first history.
+/// Only recoveries that completed inside `bounds` count towards the mean.
+pub fn recovery_stats(history: &[&Deployment], bounds: Option<(u64, u64)>) -> RecoveryStats {
+    let mut recoveries: Vec<u64> = Vec::new();
+    let mut failing_since: Option<u64> = None;
+    let mut streak = 0;
+
+    // Walk oldest to newest
+    for d in history.iter().rev() {
+        match d.status {
+            crate::network::Status::Error => {
+                failing_since.get_or_insert(d.timestamp);
+                streak += 1;
+            }
+            crate::network::Status::Ready => {
+                if let Some(start) = failing_since.take() {
+                    let recovered_at = d.timestamp + d.duration_ms;
+                    if StatPeriod::contains(bounds, recovered_at) {
+                        recoveries.push(recovered_at.saturating_sub(start));
+                    }
+                }
+                streak = 0;
+            }
+            // In-flight builds neither break nor fix anything yet
+            _ => {}
+        }
+    }
+
+    RecoveryStats {
+        mttr_s: if recoveries.is_empty() {
+            None
+        } else {
+            Some(recoveries.iter().sum::<u64>() / recoveries.len() as u64 / 1000)
+        },
+        streak,
+        broken_since: failing_since,
+    }
+}
+
+/// Keeps a table selection within `len` rows, selecting the first row when possible.
+fn clamp_selection(state: &mut ratatui::widgets::TableState, len: usize) {
+    match state.selected() {
+        Some(i) if i >= len => state.select(if len > 0 { Some(len - 1) } else { None }),
+        None if len > 0 => state.select(Some(0)),
+        _ => {}
+    }
+}
+
+/// Severity order used when sorting by status (failures first when descending)
+fn status_rank(status: &crate::network::Status) -> u8 {
+    match status {
+        crate::network::Status::Error => 4,
+        crate::network::Status::Building => 3,
+        crate::network::Status::Initializing => 2,
+        crate::network::Status::Canceled => 1,
+        crate::network::Status::Ready => 0,
+    }
+}
+
+/// Header of a deployment group with its aggregate status
+fn group_of(
+    deployments: &[Deployment],
+    items: &[usize],
+    key: String,
+    project: &str,
+    branch: Option<&str>,
+) -> DeploymentGroup {
+    let status = |i: &&usize| &deployments[**i].status;
+    DeploymentGroup {
+        key,
+        project: project.to_string(),
+        branch: branch.map(str::to_string),
+        builds: items.len(),
+        failed: items
+            .iter()
+            .filter(|i| matches!(status(i), Status::Error))
+            .count(),
+        active: items
+            .iter()
+            .filter(|i| matches!(status(i), Status::Building | Status::Initializing))
+            .count(),
+        latest: items
+            .iter()
+            .max_by_key(|&&i| deployments[i].timestamp)
+            .map(|&i| deployments[i].status.clone())
+            .unwrap_or(Status::Ready),
+    }
+}
+
+/// Expands a leading `~/` to the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), directories::BaseDirs::new()) {
+        (Some(rest), Some(dirs)) => dirs.home_dir().join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::{LogEventType, LogLine};
+
+    fn log_lines(texts: &[&str]) -> Vec<LogLine> {
+        texts
+            .iter()
+            .enumerate()
+            .map(|(i, text)| LogLine::from_raw(i as u64, LogEventType::Stdout, text))
+            .collect()
+    }
+
+    fn app_with_sections() -> App {
+        let mut app = App::new();
+        app.set_logs(log_lines(&[
+            "Cloning repository",
+            "Running \"install\" command: `npm install`",
+            "added 120 packages",
+            "Running \"build\" command: `npm run build`",
+            "Compiled successfully",
+        ]));
+        app
+    }
+
+    #[test]
+    fn clearing_logs_with_a_section_selected() {
+        let mut app = app_with_sections();
+        let pos = app
+            .log_rows
+            .iter()
+            .position(|r| *r == LogRow::Section(1))
+            .unwrap();
+        app.log_list_state.select(Some(pos));
+
+        app.clear_logs();
+        assert!(app.log_rows.is_empty());
+        assert_eq!(app.selected_log_line(), None);
+    }
+
+    #[test]
+    fn appending_logs_keeps_the_selected_section() {
+        let mut app = app_with_sections();
+        let pos = app
+            .log_rows
+            .iter()
+            .position(|r| *r == LogRow::Section(1))
+            .unwrap();
+        app.log_list_state.select(Some(pos));
+
+        app.append_logs(log_lines(&["Build completed in 12s"]));
+        assert_eq!(app.selected_log_line(), Some(3));
+    }
+
+    #[test]
+    fn stale_section_row_has_no_line() {
+        let mut app = app_with_sections();
+        app.log_rows = vec![LogRow::Section(7)];
+        app.log_list_state.select(Some(0));
+        assert_eq!(app.selected_log_line(), None);
+    }
+}
@@ -0,0 +1,39 @@
+use base64::Engine;
+use std::io::Write;
+
+// Many terminals drop OSC 52 payloads beyond ~100KB
+const OSC52_MAX_BYTES: usize = 100_000;
+
+/// Copies `text` to the clipboard.
+///
+/// OSC 52 asks the terminal itself to set the clipboard, which also works over
+/// SSH and inside tmux. Local sessions additionally go through the system
+/// clipboard, since terminals without OSC 52 support ignore it silently.
+pub fn copy(text: &str) -> Result<(), String> {
+    let is_remote =
+        std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some();
+    let osc52_sent = text.len() <= OSC52_MAX_BYTES && write_osc52(text).is_ok();
+    if is_remote && osc52_sent {
+        return Ok(());
+    }
+
+    match arboard::Clipboard::new().and_then(|mut c| c.set_text(text)) {
+        Ok(()) => Ok(()),
+        Err(_) if osc52_sent => Ok(()),
+        Err(e) => Err(format!("Clipboard unavailable: {}", e)),
+    }
+}
+
+fn write_osc52(text: &str) -> std::io::Result<()> {
+    let payload = base64::engine::general_purpose::STANDARD.encode(text);
+    let sequence = if std::env::var_os("TMUX").is_some() {
+        // tmux passthrough: wrap in DCS and double the inner ESC
+        format!("\x1bPtmux;\x1b\x1b]52;c;{}\x07\x1b\\", payload)
+    } else {
+        format!("\x1b]52;c;{}\x07", payload)
+    };
+
+    let mut stdout = std::io::stdout();
+    stdout.write_all(sequence.as_bytes())?;
+    stdout.flush()
+}
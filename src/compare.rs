@@ -0,0 +1,275 @@
+use std::sync::LazyLock;
+
+use crate::logs::{detect_sections, LogLine, LogPhase};
+use crate::network::Deployment;
+
+// Noise that differs between any two builds: timestamps, durations, hashes and IDs
+static TIMESTAMP_REGEX: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(
+        r"\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}:\d{2}(?:\.\d+)?Z?|\b\d{1,2}:\d{2}:\d{2}(?:\.\d+)?\b",
+    )
+    .unwrap()
+});
+static DURATION_REGEX: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"\b\d+(?:\.\d+)?\s?(?:ms|s|m|min|sec|seconds)\b").unwrap());
+static HASH_REGEX: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"\b[0-9a-fA-F]{7,64}\b").unwrap());
+static ID_REGEX: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r"\b(?:dpl|prj|team)_[A-Za-z0-9]+\b|\b[a-z0-9-]+\.vercel\.app\b").unwrap()
+});
+
+/// Two deployments side by side. `base` is always the older one.
+pub struct Comparison {
+    pub base: Deployment,
+    pub head: Deployment,
+    /// Distinct commits deployed after `base` up to `head`, newest first: (short SHA, message)
+    pub commits: Vec<(String, String)>,
+    /// `None` until fetched
+    pub base_logs: Option<Vec<LogLine>>,
+    pub head_logs: Option<Vec<LogLine>>,
+    pub phases: Vec<PhaseTiming>,
+    pub diff: Vec<DiffLine>,
+}
+
+pub struct PhaseTiming {
+    pub phase: LogPhase,
+    pub base_ms: Option<u64>,
+    pub head_ms: Option<u64>,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DiffKind {
+    Same,
+    Removed,
+    Added,
+}
+
+pub struct DiffLine {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
+impl Comparison {
+    /// `deployments` is every loaded deployment, used to find the commits in between
+    pub fn new(a: Deployment, b: Deployment, deployments: &[Deployment]) -> Self {
+        let (base, head) = if a.timestamp <= b.timestamp {
+            (a, b)
+        } else {
+            (b, a)
+        };
+
+        // Same branch: that branch's history. Otherwise everything the project shipped.
+        let same_branch = base.branch == head.branch;
+        let mut commits: Vec<(String, String)> = Vec::new();
+        for d in deployments {
+            if d.name != head.name
+                || d.timestamp <= base.timestamp
+                || d.timestamp > head.timestamp
+                || (same_branch && d.branch != head.branch)
+            {
+                continue;
+            }
+            let sha = d.commit_sha.as_deref().map(short_sha).unwrap_or_default();
+            if !commits.iter().any(|(s, m)| {
+                (!sha.is_empty() && *s == sha) || (sha.is_empty() && *m == d.commit_msg)
+            }) {
+                commits.push((sha, d.commit_msg.clone()));
+            }
+        }
+
+        Self {
+            base,
+            head,
+            commits,
+            base_logs: None,
+            head_logs: None,
+            phases: vec![],
+            diff: vec![],
+        }
+    }
+
+    /// Stores fetched logs for whichever side `id` is
+    pub fn set_logs(&mut self, id: &str, logs: Vec<LogLine>) {
+        if id == self.base.id {
+            self.base_logs = Some(logs);
+        } else if id == self.head.id {
+            self.head_logs = Some(logs);
+        } else {
+            return;
+        }
+        if let (Some(base), Some(head)) = (&self.base_logs, &self.head_logs) {
+            self.phases = phase_timings(base, head);
+            let normalized = |logs: &[LogLine]| -> Vec<String> {
+                logs.iter()
+                    .map(|l| normalize(&l.text))
+                    .filter(|t| !t.trim().is_empty())
+                    .collect()
+            };
+            self.diff = diff_lines(&normalized(base), &normalized(head));
+        }
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.base_logs.is_none() || self.head_logs.is_none()
+    }
+}
+
+/// First 7 characters of a commit SHA
+pub fn short_sha(sha: &str) -> String {
+    sha.chars().take(7).collect()
+}
+
+fn phase_timings(base: &[LogLine], head: &[LogLine]) -> Vec<PhaseTiming> {
+    let base_sections = detect_sections(base);
+    let head_sections = detect_sections(head);
+    let elapsed = |sections: &[crate::logs::LogSection], phase| {
+        sections
+            .iter()
+            .find(|s| s.phase == phase)
+            .map(|s| s.elapsed_ms)
+    };
+    [
+        LogPhase::Install,
+        LogPhase::Build,
+        LogPhase::Output,
+        LogPhase::Deploy,
+    ]
+    .into_iter()
+    .map(|phase| PhaseTiming {
+        phase,
+        base_ms: elapsed(&base_sections, phase),
+        head_ms: elapsed(&head_sections, phase),
+    })
+    .filter(|t| t.base_ms.is_some() || t.head_ms.is_some())
+    .collect()
+}
+
+/// Replaces the parts of a log line that change on every build, so only real
+/// differences show up in the diff
+pub fn normalize(text: &str) -> String {
+    let text = TIMESTAMP_REGEX.replace_all(text, "<time>");
+    let text = ID_REGEX.replace_all(&text, "<id>");
+    let text = DURATION_REGEX.replace_all(&text, "<dur>");
+    // Only runs with both digits and letters, so plain numbers and words survive
+    let text = HASH_REGEX.replace_all(&text, |caps: &regex::Captures| {
+        let m = &caps[0];
+        if m.chars().any(|c| c.is_ascii_digit()) && m.chars().any(|c| c.is_ascii_alphabetic()) {
+            "<hash>".to_string()
+        } else {
+            m.to_string()
+        }
+    });
+    text.trim_end().to_string()
+}
+
+/// Line diff of `a` against `b` via longest common subsequence
+pub fn diff_lines(a: &[String], b: &[String]) -> Vec<DiffLine> {
+    let (n, m) = (a.len(), b.len());
+    // lcs[i][j]: length of the LCS of a[i..] and b[j..]
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    let line = |kind, text: &String| DiffLine {
+        kind,
+        text: text.clone(),
+    };
+    while i < n && j < m {
+        if a[i] == b[j] {
+            out.push(line(DiffKind::Same, &a[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            out.push(line(DiffKind::Removed, &a[i]));
+            i += 1;
+        } else {
+            out.push(line(DiffKind::Added, &b[j]));
+            j += 1;
+        }
+    }
+    out.extend(a[i..].iter().map(|t| line(DiffKind::Removed, t)));
+    out.extend(b[j..].iter().map(|t| line(DiffKind::Added, t)));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(a: &[&str], b: &[&str]) -> Vec<(DiffKind, String)> {
+        let lines = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        diff_lines(&lines(a), &lines(b))
+            .into_iter()
+            .map(|l| (l.kind, l.text))
+            .collect()
+    }
+
+    fn line(kind: DiffKind, text: &str) -> (DiffKind, String) {
+        (kind, text.to_string())
+    }
+
+    #[test]
+    fn identical_inputs_are_all_same() {
+        assert_eq!(
+            diff(&["a", "b"], &["a", "b"]),
+            vec![line(DiffKind::Same, "a"), line(DiffKind::Same, "b")]
+        );
+        assert!(diff(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn one_side_empty() {
+        assert_eq!(
+            diff(&["a", "b"], &[]),
+            vec![line(DiffKind::Removed, "a"), line(DiffKind::Removed, "b")]
+        );
+        assert_eq!(diff(&[], &["a"]), vec![line(DiffKind::Added, "a")]);
+    }
+
+    #[test]
+    fn changed_line_is_removed_then_added() {
+        assert_eq!(
+            diff(&["a", "b", "c"], &["a", "x", "c"]),
+            vec![
+                line(DiffKind::Same, "a"),
+                line(DiffKind::Removed, "b"),
+                line(DiffKind::Added, "x"),
+                line(DiffKind::Same, "c"),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_the_longest_common_subsequence() {
+        let out = diff(&["a", "b", "c", "d"], &["b", "x", "d", "e"]);
+        assert_eq!(
+            out,
+            vec![
+                line(DiffKind::Removed, "a"),
+                line(DiffKind::Same, "b"),
+                line(DiffKind::Removed, "c"),
+                line(DiffKind::Added, "x"),
+                line(DiffKind::Same, "d"),
+                line(DiffKind::Added, "e"),
+            ]
+        );
+    }
+
+    #[test]
+    fn moved_line_shows_once_on_each_side() {
+        let out = diff(&["a", "b", "c"], &["c", "a", "b"]);
+        let same: Vec<_> = out.iter().filter(|l| l.0 == DiffKind::Same).collect();
+        assert_eq!(same.len(), 2);
+        assert!(out.contains(&line(DiffKind::Removed, "c")));
+        assert!(out.contains(&line(DiffKind::Added, "c")));
+    }
+}
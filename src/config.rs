@@ -1,9 +1,13 @@
+use crate::keymap::KeySpec;
+use crate::layout::PaneLayout;
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(default)]
 pub struct Config {
     pub theme_name: String,
     pub is_transparent: bool,
@@ -12,6 +16,22 @@ pub struct Config {
     pub last_project_name: Option<String>,
     pub enable_mouse: bool,
     pub stat_period: String,
+    /// Minutes production may stay broken before the Build Overview warns
+    pub production_alert_minutes: u64,
+    /// Name of the active body layout
+    pub layout: String,
+    /// User-defined layouts, cycled after the built-in presets
+    pub custom_layouts: Vec<PaneLayout>,
+    /// Render the colors emitted by the build instead of Polymer's own highlighting
+    pub native_log_colors: bool,
+    /// Emit OSC 8 escapes so URLs can be ctrl-clicked in supporting terminals
+    pub hyperlinks: bool,
+    /// Local checkout of each project (by project name), used to open file paths from logs
+    pub checkouts: HashMap<String, String>,
+    /// Commands last run from the command palette, most recent first
+    pub recent_commands: Vec<String>,
+    /// Keys per action name, replacing the defaults of the actions listed
+    pub keymap: HashMap<String, KeySpec>,
 }
 
 impl Default for Config {
@@ -24,6 +44,14 @@ impl Default for Config {
             last_project_name: None,
             enable_mouse: false,
             stat_period: "24h".to_string(),
+            production_alert_minutes: 60,
+            layout: "default".to_string(),
+            custom_layouts: vec![],
+            native_log_colors: true,
+            hyperlinks: true,
+            checkouts: HashMap::new(),
+            recent_commands: vec![],
+            keymap: HashMap::new(),
         }
     }
 }
@@ -0,0 +1,90 @@
+use std::io::{self, Stdout};
+use std::path::Path;
+use std::process::Command;
+
+use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{backend::CrosstermBackend, Terminal};
+
+// Editors that take `file:line:col` instead of `+line file`
+const COLON_EDITORS: &[&str] = &[
+    "code",
+    "code-insiders",
+    "codium",
+    "cursor",
+    "zed",
+    "subl",
+    "hx",
+];
+
+/// Opens `file` at `line` in `$VISUAL` / `$EDITOR` (falling back to `vi`).
+/// The TUI is suspended while the editor runs and redrawn from scratch afterwards.
+pub fn open(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    file: &Path,
+    line: Option<u32>,
+    column: Option<u32>,
+) -> Result<(), String> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    // $EDITOR may carry arguments, e.g. "code --wait"
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let mut cmd = Command::new(program);
+    cmd.args(parts);
+
+    let name = Path::new(program)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(program);
+    match line {
+        Some(line) if COLON_EDITORS.contains(&name) => {
+            if name.starts_with("code") || name == "codium" || name == "cursor" {
+                cmd.arg("--goto");
+            }
+            cmd.arg(format!(
+                "{}:{}:{}",
+                file.display(),
+                line,
+                column.unwrap_or(1)
+            ));
+        }
+        Some(line) => {
+            cmd.arg(format!("+{}", line)).arg(file);
+        }
+        None => {
+            cmd.arg(file);
+        }
+    }
+
+    suspend().map_err(|e| e.to_string())?;
+    let status = cmd.status();
+    resume(terminal).map_err(|e| e.to_string())?;
+
+    match status {
+        Ok(s) if s.success() => Ok(()),
+        Ok(s) => Err(format!("{} exited with {}", program, s)),
+        Err(e) => Err(format!("Failed to run {}: {}", program, e)),
+    }
+}
+
+fn suspend() -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        crossterm::cursor::Show
+    )
+}
+
+fn resume(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> io::Result<()> {
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+    // The editor drew over everything; force a full repaint
+    terminal.clear()
+}
@@ -0,0 +1,578 @@
+use crate::network::{Deployment, Status};
+use crate::period::StatPeriod;
+
+/// Fields that can be matched with `field:value`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Field {
+    Status,
+    Target,
+    Creator,
+    Branch,
+    Project,
+    Repo,
+    Message,
+    Id,
+    Sha,
+    Domain,
+    Since,
+}
+
+impl Field {
+    pub fn all() -> &'static [Field] {
+        &[
+            Field::Status,
+            Field::Target,
+            Field::Creator,
+            Field::Branch,
+            Field::Project,
+            Field::Repo,
+            Field::Message,
+            Field::Id,
+            Field::Sha,
+            Field::Domain,
+            Field::Since,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Field::Status => "status",
+            Field::Target => "target",
+            Field::Creator => "creator",
+            Field::Branch => "branch",
+            Field::Project => "project",
+            Field::Repo => "repo",
+            Field::Message => "msg",
+            Field::Id => "id",
+            Field::Sha => "sha",
+            Field::Domain => "domain",
+            Field::Since => "since",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Field> {
+        match name.to_lowercase().as_str() {
+            "author" => Some(Field::Creator),
+            "message" | "commit" => Some(Field::Message),
+            lower => Field::all().iter().copied().find(|f| f.name() == lower),
+        }
+    }
+
+    /// Values offered by autocompletion, newest deployments first
+    fn known_values(&self, deployments: &[Deployment]) -> Vec<String> {
+        let pick: fn(&Deployment) -> &str = match self {
+            Field::Status => {
+                return ["error", "ready", "building", "queued", "canceled"]
+                    .map(String::from)
+                    .to_vec()
+            }
+            Field::Target => return vec!["production".to_string(), "preview".to_string()],
+            Field::Since => {
+                return ["1h", "24h", "2d", "7d", "today", "yesterday"]
+                    .map(String::from)
+                    .to_vec()
+            }
+            Field::Creator => |d| &d.creator,
+            Field::Branch => |d| &d.branch,
+            Field::Project => |d| &d.name,
+            Field::Repo => |d| &d.repo,
+            Field::Domain => |d| &d.domain,
+            Field::Message | Field::Id | Field::Sha => return vec![],
+        };
+        let mut values: Vec<String> = Vec::new();
+        for d in deployments {
+            let value = pick(d);
+            if !value.is_empty() && !values.iter().any(|v| v == value) {
+                values.push(value.to_string());
+            }
+        }
+        values
+    }
+}
+
+/// One whitespace-separated piece of the query, with byte offsets for highlighting
+#[derive(Clone, Debug)]
+pub struct Token {
+    pub start: usize,
+    pub end: usize,
+    pub negated: bool,
+    /// Field name as typed, e.g. "status" in `-status:error`
+    pub field: Option<String>,
+    pub value: String,
+    pub quoted: bool,
+}
+
+/// Splits a query into tokens. Double quotes group words (`"fix login"`,
+/// `branch:"feat/a b"`); an unterminated quote runs to the end of the input.
+pub fn tokenize(query: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = query.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let mut raw = String::new();
+        let mut in_quotes = false;
+        let mut quoted = false;
+        let mut end = query.len();
+        while let Some(&(i, c)) = chars.peek() {
+            if c.is_whitespace() && !in_quotes {
+                end = i;
+                break;
+            }
+            if c == '"' {
+                in_quotes = !in_quotes;
+                quoted = true;
+            } else {
+                raw.push(c);
+            }
+            chars.next();
+        }
+
+        let (negated, rest) = match raw.strip_prefix('-') {
+            Some(rest) if !rest.is_empty() => (true, rest.to_string()),
+            _ => (false, raw),
+        };
+        // A colon inside quotes is part of the text, not a field separator
+        let field_part = query[start..end].split('"').next().unwrap_or("");
+        let (field, value) = match rest.split_once(':') {
+            Some((field, value)) if field_part.contains(':') && !field.is_empty() => {
+                (Some(field.to_string()), value.to_string())
+            }
+            _ => (None, rest),
+        };
+        tokens.push(Token {
+            start,
+            end,
+            negated,
+            field,
+            value,
+            quoted,
+        });
+    }
+    tokens
+}
+
+/// A query problem and the byte range of the query it refers to
+#[derive(Clone, PartialEq, Debug)]
+pub struct FilterError {
+    pub message: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Clone, Debug)]
+enum Matcher {
+    /// Substring of any searchable field
+    Text(String),
+    Status(Vec<Status>),
+    /// Alternatives separated by commas; each is a glob, an exact value (quoted) or a substring
+    Field(Field, Vec<Pattern>),
+    /// Epoch millis cutoff
+    Since(u64),
+}
+
+#[derive(Clone, Debug)]
+enum Pattern {
+    Glob(String),
+    Exact(String),
+    Contains(String),
+}
+
+impl Pattern {
+    fn new(value: &str, quoted: bool) -> Pattern {
+        let value = value.to_lowercase();
+        if quoted {
+            Pattern::Exact(value)
+        } else if value.contains('*') {
+            Pattern::Glob(value)
+        } else {
+            Pattern::Contains(value)
+        }
+    }
+
+    fn matches(&self, text: &str) -> bool {
+        let text = text.to_lowercase();
+        match self {
+            Pattern::Glob(p) => glob_match(p, &text),
+            Pattern::Exact(p) => text == *p,
+            Pattern::Contains(p) => text.contains(p.as_str()),
+        }
+    }
+}
+
+/// Parsed deployment filter, e.g.
+/// `status:error target:production -creator:bot branch:feat/* "fix login" since:2d`.
+/// All terms must match; `-` negates a term and commas separate alternatives.
+#[derive(Clone, Debug)]
+pub struct FilterQuery {
+    terms: Vec<(bool, Matcher)>, // (negated, matcher)
+}
+
+impl FilterQuery {
+    /// Parses `query`, keeping every valid term. Returns the first error alongside,
+    /// so a half-typed term does not empty the list.
+    pub fn parse(query: &str) -> (FilterQuery, Option<FilterError>) {
+        let mut terms = Vec::new();
+        let mut error = None;
+
+        if query.matches('"').count() % 2 == 1 {
+            let start = query.rfind('"').unwrap_or(0);
+            error = Some(FilterError {
+                message: "Unterminated quote".to_string(),
+                start,
+                end: query.len(),
+            });
+        }
+
+        for token in tokenize(query) {
+            match parse_term(&token) {
+                Ok(matcher) => terms.push((token.negated, matcher)),
+                Err(message) => {
+                    error.get_or_insert(FilterError {
+                        message,
+                        start: token.start,
+                        end: token.end,
+                    });
+                }
+            }
+        }
+        (FilterQuery { terms }, error)
+    }
+
+    pub fn matches(&self, d: &Deployment) -> bool {
+        self.terms
+            .iter()
+            .all(|(negated, matcher)| matcher_matches(matcher, d) != *negated)
+    }
+}
+
+fn parse_term(token: &Token) -> Result<Matcher, String> {
+    let Some(name) = &token.field else {
+        return Ok(Matcher::Text(token.value.to_lowercase()));
+    };
+    let field = Field::parse(name).ok_or_else(|| format!("Unknown field '{}'", name))?;
+    if token.value.is_empty() {
+        return Err(format!("'{}:' needs a value", name));
+    }
+
+    let alternatives: Vec<&str> = if token.quoted {
+        vec![token.value.as_str()]
+    } else {
+        token.value.split(',').filter(|v| !v.is_empty()).collect()
+    };
+    match field {
+        Field::Status => alternatives
+            .iter()
+            .map(|v| parse_status(v))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Matcher::Status),
+        Field::Since => {
+            let period = StatPeriod::parse(&token.value)
+                .or_else(|_| StatPeriod::parse(&format!("since {}", token.value)))
+                .map_err(|_| {
+                    format!(
+                        "Invalid since '{}' (e.g. 2d, 12h, today, 2026-10-01)",
+                        token.value
+                    )
+                })?;
+            let (start, _) = period.bounds().unwrap_or((0, u64::MAX));
+            Ok(Matcher::Since(start))
+        }
+        _ => Ok(Matcher::Field(
+            field,
+            alternatives
+                .iter()
+                .map(|v| Pattern::new(v, token.quoted))
+                .collect(),
+        )),
+    }
+}
+
+fn parse_status(value: &str) -> Result<Status, String> {
+    match value.to_lowercase().as_str() {
+        "error" | "failed" | "failure" => Ok(Status::Error),
+        "ready" | "success" | "successful" => Ok(Status::Ready),
+        "building" => Ok(Status::Building),
+        "queued" | "initializing" => Ok(Status::Initializing),
+        "canceled" | "cancelled" => Ok(Status::Canceled),
+        _ => Err(format!(
+            "Unknown status '{}' (error, ready, building, queued, canceled)",
+            value
+        )),
+    }
+}
+
+fn matcher_matches(matcher: &Matcher, d: &Deployment) -> bool {
+    match matcher {
+        Matcher::Text(text) => [
+            &d.branch,
+            &d.commit_msg,
+            &d.creator,
+            d.commit_author.as_deref().unwrap_or(""),
+            &d.target,
+            &d.repo,
+            &d.short_id,
+            &d.domain,
+        ]
+        .iter()
+        .any(|field| field.to_lowercase().contains(text.as_str())),
+        Matcher::Status(statuses) => statuses
+            .iter()
+            .any(|s| std::mem::discriminant(s) == std::mem::discriminant(&d.status)),
+        Matcher::Since(start) => d.timestamp >= *start,
+        Matcher::Field(field, patterns) => {
+            let values: Vec<&str> = match field {
+                Field::Target => vec![&d.target],
+                Field::Creator => vec![&d.creator, d.commit_author.as_deref().unwrap_or("")],
+                Field::Branch => vec![&d.branch],
+                Field::Project => vec![&d.name],
+                Field::Repo => vec![&d.repo],
+                Field::Message => vec![&d.commit_msg],
+                Field::Id => vec![&d.short_id, &d.id],
+                Field::Sha => vec![d.commit_sha.as_deref().unwrap_or("")],
+                Field::Domain => vec![&d.domain],
+                Field::Status | Field::Since => vec![],
+            };
+            patterns.iter().any(|p| values.iter().any(|v| p.matches(v)))
+        }
+    }
+}
+
+/// `*` matches any run of characters; everything else is literal
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if parts.len() == 1 {
+        return text == first;
+    }
+    if !text.starts_with(first) || text.len() < first.len() + last.len() || !text.ends_with(last) {
+        return false;
+    }
+    let mut rest = &text[first.len()..text.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Completions for the token under the cursor (the end of the query), as the full
+/// replacement text for that token: field names first, then known values of the field.
+pub fn completions(query: &str, deployments: &[Deployment]) -> Vec<String> {
+    if query.is_empty() || query.ends_with(char::is_whitespace) {
+        return vec![];
+    }
+    let Some(token) = tokenize(query).pop() else {
+        return vec![];
+    };
+    let prefix = if token.negated { "-" } else { "" };
+
+    match &token.field {
+        None => {
+            let typed = token.value.to_lowercase();
+            Field::all()
+                .iter()
+                .filter(|f| f.name().starts_with(&typed) && f.name() != typed)
+                .map(|f| format!("{}{}:", prefix, f.name()))
+                .collect()
+        }
+        Some(name) => {
+            let Some(field) = Field::parse(name) else {
+                return vec![];
+            };
+            // Complete the last comma-separated alternative
+            let (done, typed) = match token.value.rsplit_once(',') {
+                Some((done, typed)) => (format!("{},", done), typed),
+                None => (String::new(), token.value.as_str()),
+            };
+            let typed = typed.to_lowercase();
+            field
+                .known_values(deployments)
+                .into_iter()
+                .filter(|v| v.to_lowercase().starts_with(&typed) && v.to_lowercase() != typed)
+                .map(|v| {
+                    let v = if v.contains(char::is_whitespace) {
+                        format!("\"{}\"", v)
+                    } else {
+                        v
+                    };
+                    format!("{}{}:{}{}", prefix, name, done, v)
+                })
+                .collect()
+        }
+    }
+}
+
+/// Replaces the last token of `query` with `completion`
+pub fn apply_completion(query: &str, completion: &str) -> String {
+    let start = tokenize(query)
+        .last()
+        .map(|t| t.start)
+        .unwrap_or(query.len());
+    let mut result = format!("{}{}", &query[..start], completion);
+    // Completed values are finished terms; field names still need their value
+    if !completion.ends_with(':') {
+        result.push(' ');
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deployment(branch: &str, creator: &str, status: Status) -> Deployment {
+        Deployment {
+            id: "dpl_abc123".to_string(),
+            name: "web".to_string(),
+            repo: "acme/web".to_string(),
+            status,
+            commit_msg: "Fix login redirect".to_string(),
+            time: String::new(),
+            timestamp: 0,
+            duration_ms: 0,
+            domain: "web-abc123.vercel.app".to_string(),
+            branch: branch.to_string(),
+            creator: creator.to_string(),
+            target: "production".to_string(),
+            short_id: "abc123".to_string(),
+            commit_sha: Some("0123456789abcdef".to_string()),
+            provider: None,
+            org: Some("acme".to_string()),
+            commit_author: None,
+            pr_number: None,
+        }
+    }
+
+    fn matches(query: &str, d: &Deployment) -> bool {
+        let (query, error) = FilterQuery::parse(query);
+        assert_eq!(error, None);
+        query.matches(d)
+    }
+
+    #[test]
+    fn tokenize_splits_fields_negation_and_quotes() {
+        let tokens = tokenize(r#"-status:error "fix login" branch:"feat/a b""#);
+        assert_eq!(tokens.len(), 3);
+
+        assert!(tokens[0].negated);
+        assert_eq!(tokens[0].field.as_deref(), Some("status"));
+        assert_eq!(tokens[0].value, "error");
+        assert_eq!((tokens[0].start, tokens[0].end), (0, 13));
+
+        assert_eq!(tokens[1].field, None);
+        assert_eq!(tokens[1].value, "fix login");
+        assert!(tokens[1].quoted);
+
+        assert_eq!(tokens[2].field.as_deref(), Some("branch"));
+        assert_eq!(tokens[2].value, "feat/a b");
+    }
+
+    #[test]
+    fn colon_inside_quotes_is_text() {
+        let tokens = tokenize(r#""a:b""#);
+        assert_eq!(tokens[0].field, None);
+        assert_eq!(tokens[0].value, "a:b");
+    }
+
+    #[test]
+    fn lone_dash_is_not_negation() {
+        let tokens = tokenize("-");
+        assert!(!tokens[0].negated);
+        assert_eq!(tokens[0].value, "-");
+    }
+
+    #[test]
+    fn fields_match_their_values() {
+        let d = deployment("main", "alice", Status::Error);
+        assert!(matches("status:error", &d));
+        assert!(matches("status:failed,ready", &d));
+        assert!(!matches("status:ready", &d));
+        assert!(matches("target:prod", &d));
+        assert!(matches("creator:ALI", &d));
+        assert!(matches("sha:0123", &d));
+        assert!(matches("project:web repo:acme", &d));
+        assert!(!matches("branch:feat", &d));
+    }
+
+    #[test]
+    fn negation_inverts_a_term() {
+        let d = deployment("main", "dependabot", Status::Ready);
+        assert!(!matches("-creator:bot", &d));
+        assert!(matches("-creator:alice", &d));
+        assert!(matches("-status:error", &d));
+    }
+
+    #[test]
+    fn free_text_searches_all_fields() {
+        let d = deployment("main", "alice", Status::Ready);
+        assert!(matches("login", &d));
+        assert!(matches(r#""fix login""#, &d));
+        assert!(matches("abc123", &d));
+        assert!(!matches("logout", &d));
+    }
+
+    #[test]
+    fn quoted_values_match_exactly() {
+        let d = deployment("feat/login", "alice", Status::Ready);
+        assert!(matches(r#"branch:"feat/login""#, &d));
+        assert!(!matches(r#"branch:"feat""#, &d));
+        assert!(matches("branch:feat", &d));
+    }
+
+    #[test]
+    fn globs_anchor_at_both_ends() {
+        let d = deployment("feat/login", "alice", Status::Ready);
+        assert!(matches("branch:feat/*", &d));
+        assert!(matches("branch:*login", &d));
+        assert!(matches("branch:f*/*n", &d));
+        assert!(!matches("branch:login*", &d));
+        assert!(!matches("branch:*feat", &d));
+
+        assert!(glob_match("a*a", "aa"));
+        assert!(!glob_match("ab*ba", "aba"));
+        assert!(glob_match("*", ""));
+    }
+
+    #[test]
+    fn invalid_queries_keep_the_valid_terms() {
+        let d = deployment("main", "alice", Status::Error);
+
+        let (query, error) = FilterQuery::parse("status:error colour:red");
+        let error = error.unwrap();
+        assert_eq!(error.message, "Unknown field 'colour'");
+        assert_eq!((error.start, error.end), (13, 23));
+        assert!(query.matches(&d));
+
+        let (_, error) = FilterQuery::parse("status:broken");
+        assert!(error
+            .unwrap()
+            .message
+            .starts_with("Unknown status 'broken'"));
+
+        let (_, error) = FilterQuery::parse("branch:");
+        assert_eq!(error.unwrap().message, "'branch:' needs a value");
+
+        let (_, error) = FilterQuery::parse("since:soon");
+        assert!(error.unwrap().message.starts_with("Invalid since 'soon'"));
+    }
+
+    #[test]
+    fn unterminated_quote_is_reported_first() {
+        let (_, error) = FilterQuery::parse(r#"colour:red "fix"#);
+        assert_eq!(
+            error,
+            Some(FilterError {
+                message: "Unterminated quote".to_string(),
+                start: 11,
+                end: 15,
+            })
+        );
+    }
+}
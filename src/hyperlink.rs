@@ -0,0 +1,83 @@
+use std::sync::LazyLock;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+
+static URL_REGEX: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r#"https?://[^\s<>"'`]+"#).unwrap());
+
+/// A URL as drawn on screen, for mouse hit testing
+#[derive(Clone, Debug)]
+pub struct LinkRegion {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub url: String,
+}
+
+impl LinkRegion {
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        y == self.y && x >= self.x && x < self.x + self.width
+    }
+}
+
+/// Finds the URLs drawn in `area` and returns where they are. With `emit_osc8`
+/// set, their cells are also rewritten into OSC 8 hyperlinks.
+///
+/// Works on the rendered buffer rather than the log text, so wrapping, gutters and
+/// scrolling need no special handling. A URL wrapped onto the next row is only
+/// linked up to the line break.
+pub fn link_urls(buf: &mut Buffer, area: Rect, emit_osc8: bool) -> Vec<LinkRegion> {
+    let area = area.intersection(buf.area);
+    let mut links = Vec::new();
+
+    for y in area.top()..area.bottom() {
+        // Row text plus the byte offset at which each cell starts
+        let mut text = String::new();
+        let mut offsets = Vec::with_capacity(area.width as usize);
+        for x in area.left()..area.right() {
+            offsets.push(text.len());
+            text.push_str(buf[(x, y)].symbol());
+        }
+        let column = |byte: usize| offsets.partition_point(|&o| o <= byte) as u16 - 1;
+
+        for m in URL_REGEX.find_iter(&text) {
+            let url = m
+                .as_str()
+                .trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']', '}']);
+            let start = area.x + column(m.start());
+            let end = area.x + column(m.start() + url.len() - 1) + 1;
+            if emit_osc8 {
+                link(buf, Rect::new(start, y, end - start, 1), url);
+            }
+            links.push(LinkRegion {
+                x: start,
+                y,
+                width: end - start,
+                url: url.to_string(),
+            });
+        }
+    }
+
+    links
+}
+
+/// Turns the single-row `area` into an OSC 8 hyperlink to `url`.
+///
+/// Works around ratatui counting the escape sequence as visible width (ratatui
+/// issue #902): the diff then skips exactly one cell after each rewritten cell, so
+/// cells are rewritten in pairs, each carrying its neighbour's symbol. A trailing
+/// odd cell is left as plain text, since skipping the cell after it would leave
+/// that one stale.
+pub fn link(buf: &mut Buffer, area: Rect, url: &str) {
+    let area = area.intersection(buf.area);
+    let y = area.y;
+    let mut x = area.left();
+    while x + 1 < area.right() {
+        let chunk = format!("{}{}", buf[(x, y)].symbol(), buf[(x + 1, y)].symbol());
+        if chunk.is_ascii() && !chunk.contains('\x1b') {
+            buf[(x, y)].set_symbol(&format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, chunk));
+        }
+        x += 2;
+    }
+}
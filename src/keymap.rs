@@ -0,0 +1,617 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::actions::{Action, Scope};
+
+/// A single key press with its modifiers. Shift is folded into the character for
+/// printable keys (`K`, `>`), so it only appears as a modifier on named keys.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub fn from_event(key: KeyEvent) -> Self {
+        let mut modifiers = key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        if !matches!(key.code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers |= key.modifiers & KeyModifiers::SHIFT;
+        }
+        Self {
+            code: key.code,
+            modifiers,
+        }
+    }
+
+    /// Parses `k`, `K`, `ctrl-p`, `alt+enter`, `shift-tab`, `space`, `pgdn`, `f5`...
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text;
+        loop {
+            let lower = rest.to_lowercase();
+            let (modifier, len) = if lower.starts_with("ctrl-") || lower.starts_with("ctrl+") {
+                (KeyModifiers::CONTROL, 5)
+            } else if lower.starts_with("alt-") || lower.starts_with("alt+") {
+                (KeyModifiers::ALT, 4)
+            } else if lower.starts_with("shift-") || lower.starts_with("shift+") {
+                (KeyModifiers::SHIFT, 6)
+            } else {
+                break;
+            };
+            // `ctrl--` binds ctrl and the minus key
+            if rest.len() == len {
+                break;
+            }
+            modifiers |= modifier;
+            rest = &rest[len..];
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "enter" | "return" => KeyCode::Enter,
+                "tab" => KeyCode::Tab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pgup" | "pageup" => KeyCode::PageUp,
+                "pgdn" | "pagedown" => KeyCode::PageDown,
+                "esc" | "escape" => return Err("Esc is reserved for going back".to_string()),
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key \"{}\"", text)),
+                },
+            },
+        };
+
+        // Shifted characters are matched by the character itself, and terminals report
+        // ctrl/alt letters in lowercase
+        if let KeyCode::Char(c) = code {
+            let c = if modifiers.contains(KeyModifiers::SHIFT) {
+                c.to_ascii_uppercase()
+            } else if modifiers.is_empty() {
+                c
+            } else {
+                c.to_ascii_lowercase()
+            };
+            modifiers.remove(KeyModifiers::SHIFT);
+            return Ok(Self {
+                code: KeyCode::Char(c),
+                modifiers,
+            });
+        }
+        if code == KeyCode::Tab && modifiers.contains(KeyModifiers::SHIFT) {
+            modifiers.remove(KeyModifiers::SHIFT);
+            return Ok(Self {
+                code: KeyCode::BackTab,
+                modifiers,
+            });
+        }
+        Ok(Self { code, modifiers })
+    }
+
+    /// Whether the chord types a character into a text prompt
+    pub fn types_text(&self) -> bool {
+        matches!(self.code, KeyCode::Char(_))
+            && !self
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift-Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            _ => write!(f, "?"),
+        }
+    }
+}
+
+/// Keys for one action in the config: a single binding or a list of them
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum KeySpec {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeySpec {
+    fn bindings(&self) -> Vec<&str> {
+        match self {
+            KeySpec::One(s) => vec![s.as_str()],
+            KeySpec::Many(v) => v.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+struct Binding {
+    action: Action,
+    keys: Vec<KeyChord>,
+}
+
+/// Active key bindings: the defaults with the config's `keymap` entries applied
+pub struct Keymap {
+    bindings: Vec<Binding>,
+    /// Keys typed so far of a multi-key sequence
+    pending: Vec<KeyChord>,
+    /// Problems found while loading, reported once at startup
+    pub errors: Vec<String>,
+}
+
+/// Whether bindings of the two scopes can be live at the same time
+fn scopes_overlap(a: Scope, b: Scope) -> bool {
+    a == b
+        || a == Scope::Global
+        || b == Scope::Global
+        || matches!(
+            (a, b),
+            (Scope::Logs, Scope::LogViewer) | (Scope::LogViewer, Scope::Logs)
+        )
+}
+
+fn sequence_label(keys: &[KeyChord]) -> String {
+    keys.iter()
+        .map(|k| k.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parses `spec` and adds it for `action` unless its keys clash with a loaded binding.
+/// Returns whether it was added.
+fn bind(bindings: &mut Vec<Binding>, errors: &mut Vec<String>, action: Action, spec: &str) -> bool {
+    let keys: Result<Vec<KeyChord>, String> =
+        spec.split_whitespace().map(KeyChord::parse).collect();
+    let keys = match keys {
+        Ok(keys) if !keys.is_empty() => keys,
+        Ok(_) => {
+            errors.push(format!("{}: empty binding", action.id()));
+            return false;
+        }
+        Err(e) => {
+            errors.push(format!("{}: {}", action.id(), e));
+            return false;
+        }
+    };
+
+    let clash = bindings.iter().find(|b| {
+        let (a, s) = (b.action.scope(), action.scope());
+        if !scopes_overlap(a, s) {
+            return false;
+        }
+        let n = b.keys.len().min(keys.len());
+        if b.keys[..n] != keys[..n] {
+            return false;
+        }
+        // A scoped binding may shadow the same keys of a global one
+        b.keys.len() != keys.len() || a == s || (a != Scope::Global && s != Scope::Global)
+    });
+    if let Some(other) = clash {
+        errors.push(format!(
+            "\"{}\" for {} conflicts with \"{}\" for {}",
+            sequence_label(&keys),
+            action.id(),
+            sequence_label(&other.keys),
+            other.action.id()
+        ));
+        return false;
+    }
+
+    // A global binding hidden by both a list and a logs binding can never fire
+    let bound_in = |scope| {
+        bindings
+            .iter()
+            .find(|b| b.action.scope() == scope && b.keys == keys)
+            .map(|b| b.action.id())
+    };
+    let hidden = match action.scope() {
+        Scope::Global => bound_in(Scope::List)
+            .zip(bound_in(Scope::Logs))
+            .map(|(list, logs)| format!("is hidden by {} and {}", list, logs)),
+        Scope::List => bound_in(Scope::Global)
+            .zip(bound_in(Scope::Logs))
+            .map(|(global, logs)| format!("would hide {} together with {}", global, logs)),
+        Scope::Logs => bound_in(Scope::Global)
+            .zip(bound_in(Scope::List))
+            .map(|(global, list)| format!("would hide {} together with {}", global, list)),
+        _ => None,
+    };
+    if let Some(problem) = hidden {
+        errors.push(format!(
+            "\"{}\" for {} {}",
+            sequence_label(&keys),
+            action.id(),
+            problem
+        ));
+        return false;
+    }
+
+    bindings.push(Binding { action, keys });
+    true
+}
+
+impl Keymap {
+    /// `overrides` maps action names to key chords; a sequence is written with spaces
+    /// (`"g c"`). An entry replaces all default keys of that action, `[]` unbinds it.
+    /// Keys already taken by another action are rejected; if none of an entry's keys
+    /// can be used, the action keeps its defaults.
+    pub fn new(overrides: &HashMap<String, KeySpec>) -> Self {
+        let mut errors = vec![];
+        let mut names: Vec<&String> = overrides.keys().collect();
+        names.sort();
+        for name in names {
+            if Action::from_id(name).is_none() {
+                errors.push(format!("unknown action \"{}\"", name));
+            }
+        }
+
+        // Defaults first, so a user binding can never take the only key of another action
+        let mut bindings: Vec<Binding> = vec![];
+        for &action in Action::all() {
+            if !overrides.contains_key(action.id()) {
+                for spec in action.default_keys() {
+                    bind(&mut bindings, &mut errors, action, spec);
+                }
+            }
+        }
+        for &action in Action::all() {
+            let Some(spec) = overrides.get(action.id()) else {
+                continue;
+            };
+            let specs = spec.bindings();
+            let mut bound = false;
+            for spec in &specs {
+                bound |= bind(&mut bindings, &mut errors, action, spec);
+            }
+            // Only `[]` leaves an action without keys
+            if !bound && !specs.is_empty() {
+                errors.push(format!("{}: keeping the default keys", action.id()));
+                for spec in action.default_keys() {
+                    bind(&mut bindings, &mut errors, action, spec);
+                }
+            }
+        }
+
+        Self {
+            bindings,
+            pending: vec![],
+            errors,
+        }
+    }
+
+    /// Feeds one key press. `scopes` are the active scopes, most specific first.
+    /// Returns the action once a binding is complete; keys that start a longer
+    /// sequence are held until the next press.
+    pub fn feed(&mut self, key: KeyEvent, scopes: &[Scope]) -> Option<Action> {
+        let chord = KeyChord::from_event(key);
+        self.pending.push(chord);
+        loop {
+            let live = |b: &&Binding| scopes.contains(&b.action.scope());
+            let exact = scopes.iter().find_map(|scope| {
+                self.bindings
+                    .iter()
+                    .filter(live)
+                    .find(|b| b.action.scope() == *scope && b.keys == self.pending)
+            });
+            if let Some(binding) = exact {
+                self.pending.clear();
+                return Some(binding.action);
+            }
+            let is_prefix =
+                self.bindings.iter().filter(live).any(|b| {
+                    b.keys.len() > self.pending.len() && b.keys.starts_with(&self.pending)
+                });
+            if is_prefix {
+                return None;
+            }
+            // Not a known sequence: retry the last key on its own
+            if self.pending.len() > 1 {
+                self.pending = vec![chord];
+            } else {
+                self.pending.clear();
+                return None;
+            }
+        }
+    }
+
+    /// Keys typed so far of an unfinished sequence
+    pub fn pending_label(&self) -> Option<String> {
+        (!self.pending.is_empty()).then(|| sequence_label(&self.pending))
+    }
+
+    pub fn clear_pending(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Whether `key` runs `action` inside a text prompt, which takes keys directly
+    /// instead of through `feed`: only single keys that don't type text count
+    pub fn binds_in_prompt(&self, action: Action, key: KeyEvent) -> bool {
+        let chord = KeyChord::from_event(key);
+        !chord.types_text() && self.prompt_keys(action).any(|k| *k == chord)
+    }
+
+    /// Label of the keys that run `action` inside a text prompt, e.g. `F1`
+    pub fn prompt_label(&self, action: Action) -> String {
+        self.prompt_keys(action)
+            .map(|k| k.to_string())
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+
+    fn prompt_keys(&self, action: Action) -> impl Iterator<Item = &KeyChord> {
+        self.bindings
+            .iter()
+            .filter(move |b| b.action == action && b.keys.len() == 1 && !b.keys[0].types_text())
+            .map(|b| &b.keys[0])
+    }
+
+    /// Every key sequence bound to `action`, in config order
+    pub fn keys_for(&self, action: Action) -> Vec<String> {
+        self.bindings
+            .iter()
+            .filter(|b| b.action == action)
+            .map(|b| sequence_label(&b.keys))
+            .collect()
+    }
+
+    /// All keys of `action` for display, e.g. `↑ / k`; empty when unbound
+    pub fn label(&self, action: Action) -> String {
+        self.keys_for(action).join(" / ")
+    }
+
+    /// The first key of `action`, for prompts like "press K again"
+    pub fn key(&self, action: Action) -> String {
+        self.keys_for(action)
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| action.title().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(code: KeyCode, modifiers: KeyModifiers) -> KeyChord {
+        KeyChord { code, modifiers }
+    }
+
+    fn keymap(overrides: &[(&str, &str)]) -> Keymap {
+        let overrides = overrides
+            .iter()
+            .map(|(id, keys)| (id.to_string(), KeySpec::One(keys.to_string())))
+            .collect();
+        Keymap::new(&overrides)
+    }
+
+    fn press(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn parses_plain_and_named_keys() {
+        let none = KeyModifiers::NONE;
+        assert_eq!(KeyChord::parse("k"), Ok(chord(KeyCode::Char('k'), none)));
+        assert_eq!(KeyChord::parse("K"), Ok(chord(KeyCode::Char('K'), none)));
+        assert_eq!(
+            KeyChord::parse("space"),
+            Ok(chord(KeyCode::Char(' '), none))
+        );
+        assert_eq!(
+            KeyChord::parse("PageDown"),
+            Ok(chord(KeyCode::PageDown, none))
+        );
+        assert_eq!(KeyChord::parse("f5"), Ok(chord(KeyCode::F(5), none)));
+    }
+
+    #[test]
+    fn parses_modifiers() {
+        assert_eq!(
+            KeyChord::parse("ctrl-P"),
+            Ok(chord(KeyCode::Char('p'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            KeyChord::parse("Ctrl+Alt-enter"),
+            Ok(chord(
+                KeyCode::Enter,
+                KeyModifiers::CONTROL | KeyModifiers::ALT
+            ))
+        );
+        assert_eq!(
+            KeyChord::parse("ctrl--"),
+            Ok(chord(KeyCode::Char('-'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            KeyChord::parse("shift-up"),
+            Ok(chord(KeyCode::Up, KeyModifiers::SHIFT))
+        );
+    }
+
+    #[test]
+    fn shift_folds_into_the_key() {
+        assert_eq!(
+            KeyChord::parse("shift-k"),
+            Ok(chord(KeyCode::Char('K'), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            KeyChord::parse("shift-tab"),
+            Ok(chord(KeyCode::BackTab, KeyModifiers::NONE))
+        );
+        assert_eq!(
+            KeyChord::from_event(KeyEvent::new(KeyCode::Char('K'), KeyModifiers::SHIFT)),
+            KeyChord::parse("K").unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_reserved_and_unknown_keys() {
+        assert_eq!(
+            KeyChord::parse("esc"),
+            Err("Esc is reserved for going back".to_string())
+        );
+        assert_eq!(
+            KeyChord::parse("ctrl-foo"),
+            Err("unknown key \"ctrl-foo\"".to_string())
+        );
+        assert_eq!(
+            KeyChord::parse("f13"),
+            Err("unknown key \"f13\"".to_string())
+        );
+    }
+
+    #[test]
+    fn defaults_load_cleanly() {
+        assert!(keymap(&[]).errors.is_empty());
+    }
+
+    #[test]
+    fn reports_bad_entries() {
+        let map = keymap(&[("nope", "q"), ("quit", ""), ("help", "esc")]);
+        assert_eq!(
+            map.errors,
+            vec![
+                "unknown action \"nope\"",
+                "help: Esc is reserved for going back",
+                "help: keeping the default keys",
+                "quit: empty binding",
+                "quit: keeping the default keys",
+            ]
+        );
+    }
+
+    #[test]
+    fn user_binding_cannot_take_another_actions_key() {
+        let mut map = keymap(&[("themes", "q")]);
+        assert_eq!(
+            map.errors,
+            vec![
+                "\"q\" for themes conflicts with \"q\" for quit",
+                "themes: keeping the default keys",
+            ]
+        );
+        assert_eq!(map.feed(press('q'), &[Scope::Global]), Some(Action::Quit));
+        assert_eq!(map.feed(press('t'), &[Scope::Global]), Some(Action::Themes));
+    }
+
+    #[test]
+    fn rebound_actions_can_swap_keys() {
+        let mut map = keymap(&[("themes", "q"), ("quit", "t")]);
+        assert!(map.errors.is_empty());
+        assert_eq!(map.feed(press('q'), &[Scope::Global]), Some(Action::Themes));
+        assert_eq!(map.feed(press('t'), &[Scope::Global]), Some(Action::Quit));
+    }
+
+    #[test]
+    fn usable_keys_of_an_entry_are_kept() {
+        let overrides = [(
+            "quit".to_string(),
+            KeySpec::Many(vec!["t".to_string(), "Q".to_string()]),
+        )]
+        .into_iter()
+        .collect();
+        let mut map = Keymap::new(&overrides);
+        assert_eq!(
+            map.errors,
+            vec!["\"t\" for quit conflicts with \"t\" for themes"]
+        );
+        assert_eq!(map.keys_for(Action::Quit), vec!["Q"]);
+        assert_eq!(map.feed(press('q'), &[Scope::Global]), None);
+    }
+
+    #[test]
+    fn prefix_of_a_sequence_conflicts() {
+        let map = keymap(&[("quit", "g q")]);
+        assert_eq!(
+            map.errors,
+            vec![
+                "\"g q\" for quit conflicts with \"g\" for top",
+                "quit: keeping the default keys",
+            ]
+        );
+    }
+
+    #[test]
+    fn scoped_binding_shadows_a_global_one() {
+        let mut map = keymap(&[("quit", "r")]);
+        assert!(map.errors.is_empty());
+        assert_eq!(
+            map.feed(press('r'), &[Scope::List, Scope::Global]),
+            Some(Action::Redeploy)
+        );
+        assert_eq!(
+            map.feed(press('r'), &[Scope::Logs, Scope::Global]),
+            Some(Action::Quit)
+        );
+    }
+
+    #[test]
+    fn global_binding_hidden_in_both_panes_is_rejected() {
+        let mut map = keymap(&[("quit", "V")]);
+        assert_eq!(
+            map.errors,
+            vec![
+                "\"V\" for quit is hidden by mark_range and log_range",
+                "quit: keeping the default keys",
+            ]
+        );
+        assert_eq!(map.feed(press('q'), &[Scope::Global]), Some(Action::Quit));
+
+        let map = keymap(&[("mark_range", "q"), ("log_range", "q")]);
+        assert_eq!(
+            map.errors,
+            vec![
+                "\"q\" for log_range would hide quit together with mark_range",
+                "log_range: keeping the default keys",
+            ]
+        );
+    }
+
+    #[test]
+    fn feeds_multi_key_sequences() {
+        let mut map = keymap(&[("quit", "Q Q")]);
+        assert!(map.errors.is_empty());
+        let scopes = [Scope::List, Scope::Global];
+
+        assert_eq!(map.feed(press('Q'), &scopes), None);
+        assert_eq!(map.pending_label(), Some("Q".to_string()));
+        assert_eq!(map.feed(press('Q'), &scopes), Some(Action::Quit));
+        assert_eq!(map.pending_label(), None);
+
+        // A key that does not continue the sequence is tried on its own
+        assert_eq!(map.feed(press('Q'), &scopes), None);
+        assert_eq!(map.feed(press('j'), &scopes), Some(Action::Down));
+    }
+}
@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+
+/// A pane that can be placed in the body layout
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Pane {
+    Deployments,
+    Domains,
+    Stats,
+    Details,
+    Logs,
+}
+
+impl Pane {
+    /// Fixed height for panes whose content does not grow (None = flexible)
+    pub fn fixed_height(&self) -> Option<u16> {
+        match self {
+            Pane::Domains => Some(4),
+            Pane::Stats => Some(13),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PaneSlot {
+    pub pane: Pane,
+    /// Relative share of the remaining space for flexible panes
+    #[serde(default = "default_weight")]
+    pub weight: u16,
+}
+
+fn default_weight() -> u16 {
+    1
+}
+
+/// Body layout: two groups of panes split side by side (or stacked).
+/// Panes that are not listed are hidden.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PaneLayout {
+    pub name: String,
+    /// Stack the groups top to bottom instead of side by side
+    #[serde(default)]
+    pub stacked: bool,
+    /// Percentage of the body given to the primary group
+    pub split_percent: u16,
+    pub primary: Vec<PaneSlot>,
+    #[serde(default)]
+    pub secondary: Vec<PaneSlot>,
+}
+
+impl PaneLayout {
+    /// Built-in presets, in cycling order
+    pub fn presets() -> Vec<PaneLayout> {
+        vec![
+            PaneLayout {
+                name: "default".to_string(),
+                stacked: false,
+                split_percent: 50,
+                primary: vec![slot(Pane::Deployments, 1), slot(Pane::Domains, 1)],
+                secondary: vec![
+                    slot(Pane::Stats, 1),
+                    slot(Pane::Details, 3),
+                    slot(Pane::Logs, 7),
+                ],
+            },
+            PaneLayout {
+                name: "logs focus".to_string(),
+                stacked: false,
+                split_percent: 35,
+                primary: vec![slot(Pane::Deployments, 1)],
+                secondary: vec![slot(Pane::Details, 1), slot(Pane::Logs, 4)],
+            },
+            PaneLayout {
+                name: "list only".to_string(),
+                stacked: false,
+                split_percent: 100,
+                primary: vec![slot(Pane::Deployments, 1), slot(Pane::Domains, 1)],
+                secondary: vec![],
+            },
+            PaneLayout {
+                name: "vertical stack".to_string(),
+                stacked: true,
+                split_percent: 40,
+                primary: vec![slot(Pane::Deployments, 1)],
+                secondary: vec![slot(Pane::Stats, 1), slot(Pane::Logs, 1)],
+            },
+        ]
+    }
+
+    pub fn contains(&self, pane: Pane) -> bool {
+        self.primary
+            .iter()
+            .chain(self.secondary.iter())
+            .any(|s| s.pane == pane)
+    }
+}
+
+fn slot(pane: Pane, weight: u16) -> PaneSlot {
+    PaneSlot { pane, weight }
+}
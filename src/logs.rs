@@ -0,0 +1,386 @@
+use std::sync::LazyLock;
+
+use serde::Deserialize;
+
+use crate::ansi::AnsiSpan;
+
+/// Vercel build event type (`type` field of the events API)
+#[derive(Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogEventType {
+    Command,
+    #[default]
+    Stdout,
+    Stderr,
+    Exit,
+    Delimiter,
+    #[serde(other)]
+    Other,
+}
+
+/// One build log record
+#[derive(Clone, Debug)]
+pub struct LogLine {
+    /// Epoch millis
+    pub timestamp: u64,
+    pub event_type: LogEventType,
+    /// Text with escape sequences removed
+    pub text: String,
+    /// SGR color runs of `text` (empty for uncolored lines)
+    pub ansi: Vec<AnsiSpan>,
+    /// Set by `App::append_logs`, which owns the keyword regex
+    pub level: LogLevel,
+}
+
+impl LogLine {
+    pub fn from_raw(timestamp: u64, event_type: LogEventType, raw: &str) -> Self {
+        let (text, ansi) = crate::ansi::parse(raw);
+        Self {
+            timestamp,
+            event_type,
+            text,
+            ansi,
+            level: LogLevel::Info,
+        }
+    }
+}
+
+/// What the time gutter next to each log line shows
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LogTimeMode {
+    Off,
+    Absolute,
+    SinceStart,
+    Delta,
+}
+
+impl LogTimeMode {
+    pub fn next(&self) -> Self {
+        match self {
+            LogTimeMode::Off => LogTimeMode::Absolute,
+            LogTimeMode::Absolute => LogTimeMode::SinceStart,
+            LogTimeMode::SinceStart => LogTimeMode::Delta,
+            LogTimeMode::Delta => LogTimeMode::Off,
+        }
+    }
+
+    pub fn display_text(&self) -> &'static str {
+        match self {
+            LogTimeMode::Off => "Off",
+            LogTimeMode::Absolute => "Clock Time",
+            LogTimeMode::SinceStart => "Since Build Start",
+            LogTimeMode::Delta => "Delta",
+        }
+    }
+
+    /// Gutter text for a line, 9 characters wide (`None` when the gutter is off).
+    /// `previous` is the timestamp of the line before it, `start` the build start.
+    pub fn format(&self, timestamp: u64, previous: Option<u64>, start: u64) -> Option<String> {
+        let text = match self {
+            LogTimeMode::Off => return None,
+            LogTimeMode::Absolute => {
+                let time = chrono::DateTime::from_timestamp_millis(timestamp as i64)?;
+                time.with_timezone(&chrono::Local)
+                    .format("%H:%M:%S")
+                    .to_string()
+            }
+            LogTimeMode::SinceStart => {
+                let ms = timestamp.saturating_sub(start);
+                format!("+{}:{:02}.{}", ms / 60_000, ms / 1000 % 60, ms / 100 % 10)
+            }
+            LogTimeMode::Delta => {
+                format_delta(timestamp.saturating_sub(previous.unwrap_or(timestamp)))
+            }
+        };
+        Some(format!("{:>9}", text))
+    }
+}
+
+/// Compact elapsed time for the delta gutter: "+0.4s", "+12.3s", "+2m03s"
+fn format_delta(ms: u64) -> String {
+    if ms < 60_000 {
+        format!("+{}.{}s", ms / 1000, ms / 100 % 10)
+    } else {
+        format!("+{}m{:02}s", ms / 60_000, ms / 1000 % 60)
+    }
+}
+
+/// Severity of a log line, derived from the keyword group of `App::log_regex`
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum LogLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+impl LogLevel {
+    pub fn classify(text: &str, regex: &regex::Regex) -> LogLevel {
+        let mut level = LogLevel::Info;
+        for caps in regex.captures_iter(text) {
+            // Group 1: keywords
+            let Some(keyword) = caps.get(1) else {
+                continue;
+            };
+            let lower = keyword.as_str().to_lowercase();
+            if lower.contains("error") || lower.contains("fail") {
+                return LogLevel::Error;
+            } else if lower.contains("warn") {
+                level = LogLevel::Warning;
+            }
+        }
+        level
+    }
+}
+
+/// Well-known build phases, in the order they appear in a Vercel build
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum LogPhase {
+    Install,
+    Build,
+    Output,
+    Deploy,
+}
+
+impl LogPhase {
+    pub fn display_text(&self) -> &'static str {
+        match self {
+            LogPhase::Install => "Install",
+            LogPhase::Build => "Build",
+            LogPhase::Output => "Output",
+            LogPhase::Deploy => "Deploy",
+        }
+    }
+
+    /// Phase started by this line, if it is one of the known markers
+    fn detect(text: &str) -> Option<LogPhase> {
+        let lower = text.to_lowercase();
+        let has = |markers: &[&str]| markers.iter().any(|m| lower.contains(m));
+
+        if has(&[
+            "installing dependencies",
+            "running \"install\" command",
+            "npm install",
+            "npm ci",
+            "yarn install",
+            "pnpm install",
+            "bun install",
+        ]) {
+            Some(LogPhase::Install)
+        } else if has(&[
+            "running \"build\" command",
+            "run build",
+            "creating an optimized production build",
+            "next build",
+            "vite build",
+        ]) {
+            Some(LogPhase::Build)
+        } else if has(&[
+            "build completed in",
+            "collected static files",
+            "traced next.js server files",
+            "created all serverless functions",
+        ]) {
+            Some(LogPhase::Output)
+        } else if has(&[
+            "deploying outputs",
+            "uploading build outputs",
+            "deployment completed",
+        ]) {
+            Some(LogPhase::Deploy)
+        } else {
+            None
+        }
+    }
+}
+
+/// A contiguous range of log lines belonging to one build phase
+#[derive(Clone, Debug)]
+pub struct LogSection {
+    pub phase: LogPhase,
+    pub start: usize,
+    pub end: usize, // Exclusive
+    pub elapsed_ms: u64,
+}
+
+impl LogSection {
+    pub fn line_count(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+/// Splits the log into phase sections. Phases only move forward, so a later
+/// mention of e.g. "npm install" does not reopen the install section.
+/// Lines before the first marker belong to no section.
+pub fn detect_sections(lines: &[LogLine]) -> Vec<LogSection> {
+    let mut sections: Vec<LogSection> = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        let Some(phase) = LogPhase::detect(&line.text) else {
+            continue;
+        };
+        if sections.last().is_some_and(|s| phase <= s.phase) {
+            continue;
+        }
+        if let Some(prev) = sections.last_mut() {
+            prev.end = i;
+        }
+        sections.push(LogSection {
+            phase,
+            start: i,
+            end: lines.len(),
+            elapsed_ms: 0,
+        });
+    }
+
+    // A section lasts until the next one starts (or until its last line)
+    for i in 0..sections.len() {
+        let start_ts = lines[sections[i].start].timestamp;
+        let end_ts = match sections.get(i + 1) {
+            Some(next) => lines[next.start].timestamp,
+            None => lines[sections[i].end - 1].timestamp,
+        };
+        sections[i].elapsed_ms = end_ts.saturating_sub(start_ts);
+    }
+
+    sections
+}
+
+/// One row of the rendered log: a section header or a log line (indices into `App::log_sections` / `App::logs`)
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LogRow {
+    Section(usize),
+    Line(usize),
+}
+
+/// Where and why a build failed, derived from its log
+#[derive(Clone, Debug)]
+pub struct FailureSummary {
+    /// Index into the log of the first error line
+    pub line: usize,
+    pub phase: Option<LogPhase>,
+    pub exit_code: Option<i32>,
+    /// The error line and the stack trace / code frame following it
+    pub excerpt: Vec<String>,
+}
+
+const EXCERPT_LINES: usize = 4;
+
+/// Finds the first error block. The trailing "Command ... exited with 1" line is only
+/// used when nothing more specific was logged.
+pub fn find_failure(lines: &[LogLine], sections: &[LogSection]) -> Option<FailureSummary> {
+    let is_error = |l: &LogLine| l.level == LogLevel::Error;
+    let first = lines
+        .iter()
+        .position(|l| is_error(l) && exit_code(&l.text).is_none())
+        .or_else(|| lines.iter().position(|l| exit_code(&l.text).is_some()))?;
+
+    // The block runs until the next blank line; stack frames and code frames
+    // that follow a blank line still belong to it
+    let mut excerpt = Vec::new();
+    let mut after_blank = false;
+    for l in &lines[first..] {
+        if l.text.trim().is_empty() {
+            after_blank = true;
+            continue;
+        }
+        if after_blank && !is_error(l) && !is_stack_frame(&l.text) {
+            break;
+        }
+        after_blank = false;
+        excerpt.push(l.text.trim_end().to_string());
+        if excerpt.len() == EXCERPT_LINES {
+            break;
+        }
+    }
+
+    Some(FailureSummary {
+        line: first,
+        phase: sections
+            .iter()
+            .find(|s| first >= s.start && first < s.end)
+            .map(|s| s.phase),
+        exit_code: lines.iter().rev().find_map(|l| exit_code(&l.text)),
+        excerpt,
+    })
+}
+
+/// Stack trace or code frame lines: "    at foo (file.js:1:2)", "> 12 | code", "    |   ^"
+fn is_stack_frame(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    trimmed.starts_with("at ")
+        || trimmed.starts_with('|')
+        || trimmed.starts_with('^')
+        || trimmed.starts_with('>')
+        || trimmed.starts_with("File \"")
+        || trimmed.starts_with("--> ")
+        || trimmed
+            .split_once(" |")
+            .is_some_and(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Exit code from lines like `Command "npm run build" exited with 1` or `exit code: 2`
+fn exit_code(text: &str) -> Option<i32> {
+    let lower = text.to_lowercase();
+    ["exited with ", "exit code", "exit status"]
+        .iter()
+        .find_map(|marker| {
+            let rest = &lower[lower.find(marker)? + marker.len()..];
+            let digits: String = rest
+                .trim_start_matches([':', ' '])
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .collect();
+            digits.parse().ok()
+        })
+        .filter(|code| *code != 0)
+}
+
+static FILE_REF_REGEX: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(
+        r"((?:\.{1,2}/|/)?(?:(?:[\w@.\-\[\]]+|\([\w\-]+\))/)*[\w@\-\[\].]*\.[A-Za-z][A-Za-z0-9]*)(?::(\d+)(?::(\d+))?| ?\((\d+):(\d+)\))?",
+    )
+    .unwrap()
+});
+
+/// Directories Vercel checks projects out into; paths below them are relative to the repo root
+const BUILD_ROOTS: &[&str] = &["/vercel/path0/", "/vercel/path1/", "/vercel/workpath0/"];
+
+/// A source location mentioned in a log line, e.g. `./src/app/page.tsx:12:3`
+#[derive(Clone, PartialEq, Debug)]
+pub struct FileRef {
+    /// Relative to the repository root
+    pub path: String,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+}
+
+/// File references in `text`, in order. Candidates are loose (anything with an
+/// extension), so callers should check which ones exist.
+pub fn file_refs(text: &str) -> Vec<FileRef> {
+    FILE_REF_REGEX
+        .captures_iter(text)
+        .filter_map(|caps| {
+            let m = caps.get(1)?;
+            // Skip the path part of URLs ("https://host/a.js")
+            if text[..m.start()].ends_with([':', '/']) {
+                return None;
+            }
+            let mut path = m.as_str();
+            for root in BUILD_ROOTS {
+                path = path.strip_prefix(root).unwrap_or(path);
+            }
+            let path = path.trim_start_matches("./").trim_start_matches('/');
+            let number = |groups: [usize; 2]| {
+                groups
+                    .iter()
+                    .find_map(|&g| caps.get(g))
+                    .and_then(|m| m.as_str().parse().ok())
+            };
+            Some(FileRef {
+                path: path.to_string(),
+                line: number([2, 4]),
+                column: number([3, 5]),
+            })
+        })
+        .collect()
+}
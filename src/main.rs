@@ -4,7 +4,7 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use ratatui::{backend::CrosstermBackend, style::Color, Terminal};
+use ratatui::{backend::CrosstermBackend, style::Color, widgets::TableState, Terminal};
 use std::{
     io::{self, Write},
     time::Duration,
@@ -12,14 +12,29 @@ use std::{
 use tokio::sync::mpsc;
 use tokio::time;
 
+mod actions;
+mod ansi;
 mod app;
+mod clipboard;
+mod compare;
 mod config;
+mod editor;
+mod filter;
+mod hyperlink;
+mod keymap;
+mod layout;
+mod logs;
 mod network;
+mod period;
 mod theme;
 mod ui;
 
-use app::{ActivePane, App, ConfirmationState, ContextMenu};
-use network::{Network, NetworkEvent};
+use actions::{Action, HelpSection, Scope};
+use app::{
+    ActivePane, App, BreakdownColumn, ConfirmationState, ContextMenu, DashboardColumn, GitLink,
+    ViewMode,
+};
+use network::{BatchAction, Network, NetworkEvent};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -105,6 +120,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         token_input.to_string()
     };
 
+    // Create App State
+    let mut app = App::new();
+
+    // Keymap problems are shown before the TUI takes over the screen
+    if !app.keymap.errors.is_empty() {
+        println!("\x1b[1;33mProblems in the keymap config:\x1b[0m");
+        for error in &app.keymap.errors {
+            println!("  - {}", error);
+        }
+        println!();
+        println!("The bindings above were skipped. Press Enter to continue...");
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+    }
+
     // 1. Setup Terminal AFTER Auth
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -117,9 +147,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create App State
-    let mut app = App::new();
-
     // Setup Network Thread
     let (tx, mut rx) = mpsc::channel(100);
     // Command Channel
@@ -143,6 +170,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _initial_proj = app.current_project_id.clone();
 
     loop {
+        // A new period may need older history than the dashboard has
+        if app.view_mode == ViewMode::Dashboard
+            && !app.projects.is_empty()
+            && app.dashboard_period != Some(app.stat_period)
+        {
+            fetch_dashboard(&mut app, &cmd_tx).await;
+        }
+
         // Ensure stats are up-to-date with current selection/time
         app.update_stats();
         terminal.draw(|f| ui::draw(f, &mut app))?;
@@ -153,347 +188,384 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         if crossterm::event::poll(timeout)? {
             match event::read()? {
-                Event::Mouse(mouse_event) => {
-                    if app.enable_mouse {
-                        // --- Context Menu Handling (Priority) ---
-                        if let Some(menu) = &mut app.context_menu {
-                            let menu_x = menu.position.0;
-                            let menu_y = menu.position.1;
-                            let menu_w = 20;
-                            let menu_h = menu.options.len() as u16 + 2;
-
-                            match mouse_event.kind {
-                                event::MouseEventKind::Moved => {
-                                    let mx = mouse_event.column;
-                                    let my = mouse_event.row;
-                                    // Hover selection
-                                    if mx >= menu_x
-                                        && mx < menu_x + menu_w
-                                        && my >= menu_y
-                                        && my < menu_y + menu_h
-                                    {
-                                        let hovered_index =
-                                            (my.saturating_sub(menu_y).saturating_sub(1)) as usize;
-                                        if hovered_index < menu.options.len() {
-                                            menu.selected_index = hovered_index;
-                                        }
+                Event::Mouse(mouse_event) if app.enable_mouse => {
+                    // --- Context Menu Handling (Priority) ---
+                    if let Some(menu) = &mut app.context_menu {
+                        let menu_x = menu.position.0;
+                        let menu_y = menu.position.1;
+                        let menu_w = 20;
+                        let menu_h = menu.options.len() as u16 + 2;
+
+                        match mouse_event.kind {
+                            event::MouseEventKind::Moved => {
+                                let mx = mouse_event.column;
+                                let my = mouse_event.row;
+                                // Hover selection
+                                if mx >= menu_x
+                                    && mx < menu_x + menu_w
+                                    && my >= menu_y
+                                    && my < menu_y + menu_h
+                                {
+                                    let hovered_index =
+                                        (my.saturating_sub(menu_y).saturating_sub(1)) as usize;
+                                    if hovered_index < menu.options.len() {
+                                        menu.selected_index = hovered_index;
                                     }
                                 }
-                                event::MouseEventKind::ScrollUp => {
-                                    if menu.selected_index > 0 {
-                                        menu.selected_index -= 1;
-                                    }
+                            }
+                            event::MouseEventKind::ScrollUp if menu.selected_index > 0 => {
+                                menu.selected_index -= 1;
+                            }
+                            event::MouseEventKind::ScrollDown
+                                if menu.selected_index < menu.options.len() - 1 =>
+                            {
+                                menu.selected_index += 1;
+                            }
+                            event::MouseEventKind::Down(event::MouseButton::Left) => {
+                                let mx = mouse_event.column;
+                                let my = mouse_event.row;
+
+                                if mx >= menu_x
+                                    && mx < menu_x + menu_w
+                                    && my >= menu_y
+                                    && my < menu_y + menu_h
+                                {
+                                    // Clicked inside menu
+                                    // Action is performed on the *currently selected* item (which should match hover)
+                                    let option = menu.options[menu.selected_index];
+                                    let id = menu.deployment_id.clone();
+                                    app.context_menu = None; // Close after action
+                                    run_menu_option(&mut app, option, &id, &cmd_tx, &mut terminal)
+                                        .await;
+                                } else {
+                                    // Clicked outside
+                                    app.context_menu = None;
                                 }
-                                event::MouseEventKind::ScrollDown => {
-                                    if menu.selected_index < menu.options.len() - 1 {
-                                        menu.selected_index += 1;
+                            }
+                            _ => {}
+                        }
+                        // Swallow interactions when menu is open
+                        continue;
+                    }
+
+                    match mouse_event.kind {
+                        event::MouseEventKind::ScrollUp => {
+                            let mx = mouse_event.column;
+                            let my = mouse_event.row;
+
+                            let in_logs = mx >= app.logs_area.x
+                                && mx < app.logs_area.x + app.logs_area.width
+                                && my >= app.logs_area.y
+                                && my < app.logs_area.y + app.logs_area.height;
+                            let in_deployments = mx >= app.deployments_area.x
+                                && mx < app.deployments_area.x + app.deployments_area.width
+                                && my >= app.deployments_area.y
+                                && my < app.deployments_area.y + app.deployments_area.height;
+
+                            if in_logs {
+                                let i = match app.log_list_state.selected() {
+                                    Some(i) => {
+                                        if i == 0 {
+                                            0
+                                        } else {
+                                            i - 1
+                                        }
                                     }
-                                }
-                                event::MouseEventKind::Down(event::MouseButton::Left) => {
-                                    let mx = mouse_event.column;
-                                    let my = mouse_event.row;
-
-                                    if mx >= menu_x
-                                        && mx < menu_x + menu_w
-                                        && my >= menu_y
-                                        && my < menu_y + menu_h
-                                    {
-                                        // Clicked inside menu
-                                        // Action is performed on the *currently selected* item (which should match hover)
-                                        let option = &menu.options[menu.selected_index];
-                                        match option.as_str() {
-                                            "Open in Browser" => {
-                                                if let Some(d) = app
-                                                    .deployments
-                                                    .iter()
-                                                    .find(|d| d.id == menu.deployment_id)
-                                                {
-                                                    let url = format!("https://{}", d.domain);
-                                                    let _ = webbrowser::open(&url);
+                                    None => app.log_rows.len().saturating_sub(1),
+                                };
+                                app.log_list_state.select(Some(i));
+                            } else if in_deployments {
+                                let i = match app._list_state.selected() {
+                                    Some(i) => {
+                                        if i == 0 {
+                                            app.list_rows.len().saturating_sub(1)
+                                        } else {
+                                            i - 1
+                                        }
+                                    }
+                                    None => 0,
+                                };
+                                app._list_state.select(Some(i));
+                            } else {
+                                // Fallback to active pane
+                                match app.active_pane {
+                                    ActivePane::Deployments => {
+                                        let i = match app._list_state.selected() {
+                                            Some(i) => {
+                                                if i == 0 {
+                                                    app.list_rows.len().saturating_sub(1)
+                                                } else {
+                                                    i - 1
                                                 }
                                             }
-                                            "Redeploy" => {
-                                                app.confirmation_mode =
-                                                    ConfirmationState::RedeployPending(
-                                                        menu.deployment_id.clone(),
-                                                        std::time::Instant::now(),
-                                                    );
-                                            }
-                                            "Kill" => {
-                                                // Only allow if building
-                                                if let Some(d) = app
-                                                    .deployments
-                                                    .iter()
-                                                    .find(|d| d.id == menu.deployment_id)
-                                                {
-                                                    if matches!(d.status, network::Status::Building)
-                                                    {
-                                                        app.confirmation_mode =
-                                                            ConfirmationState::CancelPending(
-                                                                menu.deployment_id.clone(),
-                                                                std::time::Instant::now(),
-                                                            );
-                                                    }
+                                            None => 0,
+                                        };
+                                        app._list_state.select(Some(i));
+                                    }
+                                    ActivePane::Logs => {
+                                        let i = match app.log_list_state.selected() {
+                                            Some(i) => {
+                                                if i == 0 {
+                                                    0
+                                                } else {
+                                                    i - 1
                                                 }
                                             }
-                                            _ => {}
-                                        }
-                                        app.context_menu = None; // Close after action
-                                    } else {
-                                        // Clicked outside
-                                        app.context_menu = None;
+                                            None => app.log_rows.len().saturating_sub(1),
+                                        };
+                                        app.log_list_state.select(Some(i));
                                     }
                                 }
-                                _ => {}
                             }
-                            // Swallow interactions when menu is open
-                            continue;
                         }
-
-                        match mouse_event.kind {
-                            event::MouseEventKind::ScrollUp => {
-                                let mx = mouse_event.column;
-                                let my = mouse_event.row;
-
-                                let in_logs = mx >= app.logs_area.x
-                                    && mx < app.logs_area.x + app.logs_area.width
-                                    && my >= app.logs_area.y
-                                    && my < app.logs_area.y + app.logs_area.height;
-                                let in_deployments = mx >= app.deployments_area.x
-                                    && mx < app.deployments_area.x + app.deployments_area.width
-                                    && my >= app.deployments_area.y
-                                    && my < app.deployments_area.y + app.deployments_area.height;
-
-                                if in_logs {
-                                    let i = match app.log_list_state.selected() {
-                                        Some(i) => {
-                                            if i == 0 {
-                                                0
-                                            } else {
-                                                i - 1
-                                            }
+                        event::MouseEventKind::ScrollDown => {
+                            let mx = mouse_event.column;
+                            let my = mouse_event.row;
+
+                            let in_logs = mx >= app.logs_area.x
+                                && mx < app.logs_area.x + app.logs_area.width
+                                && my >= app.logs_area.y
+                                && my < app.logs_area.y + app.logs_area.height;
+                            let in_deployments = mx >= app.deployments_area.x
+                                && mx < app.deployments_area.x + app.deployments_area.width
+                                && my >= app.deployments_area.y
+                                && my < app.deployments_area.y + app.deployments_area.height;
+
+                            if in_logs {
+                                let i = match app.log_list_state.selected() {
+                                    Some(i) => {
+                                        if i >= app.log_rows.len().saturating_sub(1) {
+                                            app.log_rows.len().saturating_sub(1)
+                                        } else {
+                                            i + 1
                                         }
-                                        None => app.logs.len().saturating_sub(1),
-                                    };
-                                    app.log_list_state.select(Some(i));
-                                } else if in_deployments {
-                                    let i = match app._list_state.selected() {
-                                        Some(i) => {
-                                            if i == 0 {
-                                                app.filtered_deployments.len().saturating_sub(1)
-                                            } else {
-                                                i - 1
-                                            }
+                                    }
+                                    None => 0,
+                                };
+                                app.log_list_state.select(Some(i));
+                            } else if in_deployments {
+                                let i = match app._list_state.selected() {
+                                    Some(i) => {
+                                        if i >= app.list_rows.len().saturating_sub(1) {
+                                            0
+                                        } else {
+                                            i + 1
                                         }
-                                        None => 0,
-                                    };
-                                    app._list_state.select(Some(i));
-                                } else {
-                                    // Fallback to active pane
-                                    match app.active_pane {
-                                        ActivePane::Deployments => {
-                                            let i = match app._list_state.selected() {
-                                                Some(i) => {
-                                                    if i == 0 {
-                                                        app.filtered_deployments
-                                                            .len()
-                                                            .saturating_sub(1)
-                                                    } else {
-                                                        i - 1
-                                                    }
+                                    }
+                                    None => 0,
+                                };
+                                app._list_state.select(Some(i));
+                            } else {
+                                match app.active_pane {
+                                    ActivePane::Deployments => {
+                                        let i = match app._list_state.selected() {
+                                            Some(i) => {
+                                                if i >= app.list_rows.len().saturating_sub(1) {
+                                                    0
+                                                } else {
+                                                    i + 1
                                                 }
-                                                None => 0,
-                                            };
-                                            app._list_state.select(Some(i));
-                                        }
-                                        ActivePane::Logs => {
-                                            let i = match app.log_list_state.selected() {
-                                                Some(i) => {
-                                                    if i == 0 {
-                                                        0
-                                                    } else {
-                                                        i - 1
-                                                    }
+                                            }
+                                            None => 0,
+                                        };
+                                        app._list_state.select(Some(i));
+                                    }
+                                    ActivePane::Logs => {
+                                        let i = match app.log_list_state.selected() {
+                                            Some(i) => {
+                                                if i >= app.log_rows.len().saturating_sub(1) {
+                                                    app.log_rows.len().saturating_sub(1)
+                                                } else {
+                                                    i + 1
                                                 }
-                                                None => app.logs.len().saturating_sub(1),
-                                            };
-                                            app.log_list_state.select(Some(i));
-                                        }
+                                            }
+                                            None => 0,
+                                        };
+                                        app.log_list_state.select(Some(i));
                                     }
                                 }
                             }
-                            event::MouseEventKind::ScrollDown => {
-                                let mx = mouse_event.column;
-                                let my = mouse_event.row;
-
-                                let in_logs = mx >= app.logs_area.x
-                                    && mx < app.logs_area.x + app.logs_area.width
-                                    && my >= app.logs_area.y
-                                    && my < app.logs_area.y + app.logs_area.height;
-                                let in_deployments = mx >= app.deployments_area.x
-                                    && mx < app.deployments_area.x + app.deployments_area.width
-                                    && my >= app.deployments_area.y
-                                    && my < app.deployments_area.y + app.deployments_area.height;
-
-                                if in_logs {
-                                    let i = match app.log_list_state.selected() {
-                                        Some(i) => {
-                                            if i >= app.logs.len().saturating_sub(1) {
-                                                app.logs.len().saturating_sub(1)
-                                            } else {
-                                                i + 1
-                                            }
-                                        }
-                                        None => 0,
-                                    };
-                                    app.log_list_state.select(Some(i));
-                                } else if in_deployments {
-                                    let i = match app._list_state.selected() {
-                                        Some(i) => {
-                                            if i >= app.filtered_deployments.len().saturating_sub(1)
-                                            {
-                                                0
+                        }
+                        event::MouseEventKind::Down(event::MouseButton::Left) => {
+                            // Hit testing for Deployments
+                            let mx = mouse_event.column;
+                            let my = mouse_event.row;
+
+                            // Check if inside deployments area
+                            let r = app.deployments_area;
+                            if mx >= r.x && mx < r.x + r.width && my >= r.y && my < r.y + r.height {
+                                if let Some(target_index) = app.list_row_at(mx, my) {
+                                    app._list_state.select(Some(target_index));
+                                    app.active_pane = ActivePane::Deployments;
+
+                                    // Double Click Detection
+                                    let now = std::time::Instant::now();
+                                    if let Some((last_time, lx, ly)) = app.last_click {
+                                        if now.duration_since(last_time)
+                                            < Duration::from_millis(500)
+                                            && lx == mx
+                                            && ly == my
+                                        {
+                                            // Double Click Action: Open Browser (or fold a group)
+                                            if let Some(d) = app.deployment_at(target_index) {
+                                                let url = format!("https://{}", d.domain);
+                                                let _ = webbrowser::open(&url);
                                             } else {
-                                                i + 1
+                                                app.toggle_selected_group();
                                             }
+                                            app.last_click = None; // Reset
+                                        } else {
+                                            app.last_click = Some((now, mx, my));
                                         }
-                                        None => 0,
-                                    };
-                                    app._list_state.select(Some(i));
-                                } else {
-                                    match app.active_pane {
-                                        ActivePane::Deployments => {
-                                            let i = match app._list_state.selected() {
-                                                Some(i) => {
-                                                    if i >= app
-                                                        .filtered_deployments
-                                                        .len()
-                                                        .saturating_sub(1)
-                                                    {
-                                                        0
-                                                    } else {
-                                                        i + 1
-                                                    }
-                                                }
-                                                None => 0,
-                                            };
-                                            app._list_state.select(Some(i));
-                                        }
-                                        ActivePane::Logs => {
-                                            let i = match app.log_list_state.selected() {
-                                                Some(i) => {
-                                                    if i >= app.logs.len().saturating_sub(1) {
-                                                        app.logs.len().saturating_sub(1)
-                                                    } else {
-                                                        i + 1
-                                                    }
-                                                }
-                                                None => 0,
-                                            };
-                                            app.log_list_state.select(Some(i));
-                                        }
+                                    } else {
+                                        app.last_click = Some((now, mx, my));
                                     }
                                 }
+                            } else if let Some(link) =
+                                app.log_links.iter().find(|l| l.contains(mx, my))
+                            {
+                                // Click on a URL in the logs: open it, like `o` does for deployments
+                                let url = link.url.clone();
+                                let colors = app.current_theme.get_colors();
+                                app.toast_message = Some(match webbrowser::open(&url) {
+                                    Ok(()) => (
+                                        format!("Opened {}", url),
+                                        colors.status_success,
+                                        std::time::Instant::now(),
+                                    ),
+                                    Err(e) => (
+                                        format!("Failed to open browser: {}", e),
+                                        colors.status_error,
+                                        std::time::Instant::now(),
+                                    ),
+                                });
+                                app.last_click = None;
+                            } else {
+                                app.last_click = None;
+                                app.context_menu = None; // Click outside closes menu
+                                app.confirmation_mode = ConfirmationState::None;
                             }
-                            event::MouseEventKind::Down(event::MouseButton::Left) => {
-                                // Hit testing for Deployments
-                                let mx = mouse_event.column;
-                                let my = mouse_event.row;
-
-                                // Check if inside deployments area
-                                let r = app.deployments_area;
-                                if mx >= r.x
-                                    && mx < r.x + r.width
-                                    && my >= r.y
-                                    && my < r.y + r.height
-                                {
-                                    let row_in_list = my.saturating_sub(r.y);
-                                    let item_stride = 6;
-                                    if row_in_list > 0 {
-                                        let clicked_offset = (row_in_list as usize) / item_stride;
-                                        let target_index = app.scroll_offset + clicked_offset;
-
-                                        if target_index < app.filtered_deployments.len() {
-                                            app._list_state.select(Some(target_index));
-                                            app.active_pane = ActivePane::Deployments;
-
-                                            // Double Click Detection
-                                            let now = std::time::Instant::now();
-                                            if let Some((last_time, lx, ly)) = app.last_click {
-                                                if now.duration_since(last_time)
-                                                    < Duration::from_millis(500)
-                                                    && lx == mx
-                                                    && ly == my
-                                                {
-                                                    // Double Click Action: Open Browser
-                                                    if let Some(d) =
-                                                        app.filtered_deployments.get(target_index)
-                                                    {
-                                                        let url = format!("https://{}", d.domain);
-                                                        let _ = webbrowser::open(&url);
-                                                    }
-                                                    app.last_click = None; // Reset
-                                                } else {
-                                                    app.last_click = Some((now, mx, my));
-                                                }
-                                            } else {
-                                                app.last_click = Some((now, mx, my));
-                                            }
-                                        }
+                        }
+                        event::MouseEventKind::Down(event::MouseButton::Right) => {
+                            // Right Click -> Context Menu
+                            let mx = mouse_event.column;
+                            let my = mouse_event.row;
+                            let r = app.deployments_area;
+
+                            if mx >= r.x && mx < r.x + r.width && my >= r.y && my < r.y + r.height {
+                                if let Some(target_index) = app.list_row_at(mx, my) {
+                                    // Select item
+                                    app._list_state.select(Some(target_index));
+                                    app.active_pane = ActivePane::Deployments;
+
+                                    // Group headers have no menu
+                                    if let Some(d) = app.deployment_at(target_index) {
+                                        app.context_menu = Some(ContextMenu {
+                                            position: (mx, my),
+                                            deployment_id: d.id.clone(),
+                                            selected_index: 0,
+                                            options: vec![
+                                                Action::OpenBrowser,
+                                                Action::Redeploy,
+                                                Action::Cancel,
+                                            ],
+                                        });
                                     }
-                                } else {
-                                    app.last_click = None;
-                                    app.context_menu = None; // Click outside closes menu
-                                    app.confirmation_mode = ConfirmationState::None;
                                 }
                             }
-                            event::MouseEventKind::Down(event::MouseButton::Right) => {
-                                // Right Click -> Context Menu
-                                let mx = mouse_event.column;
-                                let my = mouse_event.row;
-                                let r = app.deployments_area;
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Key(key) => {
+                    // Esc always abandons a half-typed key sequence
+                    if key.code == KeyCode::Esc {
+                        app.keymap.clear_pending();
+                    }
 
-                                if mx >= r.x
-                                    && mx < r.x + r.width
-                                    && my >= r.y
-                                    && my < r.y + r.height
-                                {
-                                    let row_in_list = my.saturating_sub(r.y);
-                                    let item_stride = 6;
-                                    if row_in_list > 0 {
-                                        let clicked_offset = (row_in_list as usize) / item_stride;
-                                        let target_index = app.scroll_offset + clicked_offset;
-
-                                        if target_index < app.filtered_deployments.len() {
-                                            // Select item
-                                            app._list_state.select(Some(target_index));
-                                            app.active_pane = ActivePane::Deployments;
+                    // --- Help Overlay (Traps Focus) ---
+                    if app.show_help {
+                        // Esc closes like the overlay's own key
+                        let action = match key.code {
+                            KeyCode::Esc => Some(Action::Help),
+                            _ => app.action_for_key(key),
+                        };
+                        match action {
+                            Some(Action::Help | Action::Quit) => app.show_help = false,
+                            Some(Action::Up) => {
+                                app.help_scroll = app.help_scroll.saturating_sub(1);
+                            }
+                            Some(Action::Down) => {
+                                app.help_scroll = app.help_scroll.saturating_add(1);
+                            }
+                            Some(Action::PageUp) => {
+                                app.help_scroll = app.help_scroll.saturating_sub(20);
+                            }
+                            Some(Action::PageDown) => {
+                                app.help_scroll = app.help_scroll.saturating_add(20);
+                            }
+                            Some(Action::Top) => app.help_scroll = 0,
+                            // Clamped to the content when drawing
+                            Some(Action::Bottom) => app.help_scroll = u16::MAX,
+                            _ => {}
+                        }
+                        continue;
+                    }
 
-                                            let d = &app.filtered_deployments[target_index];
-
-                                            // Open Menu
-                                            app.context_menu = Some(ContextMenu {
-                                                position: (mx, my),
-                                                deployment_id: d.id.clone(),
-                                                selected_index: 0,
-                                                options: vec![
-                                                    "Open in Browser".to_string(),
-                                                    "Redeploy".to_string(),
-                                                    "Kill".to_string(),
-                                                ],
-                                            });
-                                        }
-                                    }
+                    // --- Command Palette (Traps Focus) ---
+                    if app.show_command_palette {
+                        match key.code {
+                            KeyCode::Esc => app.show_command_palette = false,
+                            _ if app.keymap.binds_in_prompt(Action::Help, key) => {
+                                app.open_help(vec![HelpSection::Prompts])
+                            }
+                            KeyCode::Enter => {
+                                let action = app
+                                    .palette_list_state
+                                    .selected()
+                                    .and_then(|i| app.palette_matches.get(i).copied());
+                                app.show_command_palette = false;
+                                if let Some(action) = action {
+                                    app.record_recent_action(action);
+                                    run_action(&mut app, action, None, &cmd_tx, &mut terminal)
+                                        .await;
                                 }
                             }
+                            KeyCode::Up => {
+                                let i = app
+                                    .palette_list_state
+                                    .selected()
+                                    .unwrap_or(0)
+                                    .saturating_sub(1);
+                                app.palette_list_state.select(Some(i));
+                            }
+                            KeyCode::Down => {
+                                let last = app.palette_matches.len().saturating_sub(1);
+                                let i = match app.palette_list_state.selected() {
+                                    Some(i) => (i + 1).min(last),
+                                    None => 0,
+                                };
+                                app.palette_list_state.select(Some(i));
+                            }
+                            KeyCode::Backspace => {
+                                app.palette_query.pop();
+                                app.update_palette();
+                            }
+                            KeyCode::Char(c) => {
+                                app.palette_query.push(c);
+                                app.update_palette();
+                            }
                             _ => {}
                         }
+                        continue;
                     }
-                }
-                Event::Key(key) => {
+
                     // --- Filter Mode (Traps Focus) ---
                     if app.is_filter_mode {
                         match key.code {
+                            _ if app.keymap.binds_in_prompt(Action::Help, key) => {
+                                app.open_help(vec![HelpSection::Prompts])
+                            }
                             KeyCode::Esc => {
                                 app.is_filter_mode = false;
                                 app.filter_query.clear();
@@ -504,6 +576,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 app.is_filter_mode = false;
                                 // Keep query active
                             }
+                            KeyCode::Tab => {
+                                if let Some(completion) =
+                                    filter::completions(&app.filter_query, &app.deployments).first()
+                                {
+                                    app.filter_query =
+                                        filter::apply_completion(&app.filter_query, completion);
+                                    app.update_filter();
+                                    app._list_state.select(Some(0));
+                                }
+                            }
                             KeyCode::Backspace => {
                                 app.filter_query.pop();
                                 app.update_filter();
@@ -519,53 +601,105 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         continue;
                     }
 
-                    // Handle Context Menu Keys
-                    if let Some(menu) = &mut app.context_menu {
+                    // --- Log Search Input (Traps Focus) ---
+                    if app.is_log_search_mode {
+                        match key.code {
+                            _ if app.keymap.binds_in_prompt(Action::Help, key) => {
+                                app.open_help(vec![HelpSection::Prompts])
+                            }
+                            KeyCode::Esc => app.clear_log_search(),
+                            KeyCode::Enter => app.is_log_search_mode = false, // Keep hits
+                            KeyCode::Backspace => {
+                                let mut query = app.log_search_query.clone();
+                                query.pop();
+                                app.set_log_search(query);
+                            }
+                            KeyCode::Char(c) => {
+                                let query = format!("{}{}", app.log_search_query, c);
+                                app.set_log_search(query);
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    // --- Log Jump-to-Line Input (Traps Focus) ---
+                    if app.is_log_jump_mode {
                         match key.code {
-                            KeyCode::Esc => app.context_menu = None,
-                            KeyCode::Up | KeyCode::Char('k') => {
-                                if menu.selected_index > 0 {
-                                    menu.selected_index -= 1;
+                            _ if app.keymap.binds_in_prompt(Action::Help, key) => {
+                                app.open_help(vec![HelpSection::Prompts])
+                            }
+                            KeyCode::Esc => app.is_log_jump_mode = false,
+                            KeyCode::Enter => {
+                                if let Ok(line) = app.log_jump_input.parse::<usize>() {
+                                    app.jump_to_log_line(line);
                                 }
+                                app.is_log_jump_mode = false;
+                            }
+                            KeyCode::Backspace => {
+                                app.log_jump_input.pop();
                             }
-                            KeyCode::Down | KeyCode::Char('j') => {
-                                if menu.selected_index < menu.options.len() - 1 {
-                                    menu.selected_index += 1;
+                            KeyCode::Char(c) if c.is_ascii_digit() => app.log_jump_input.push(c),
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    // --- Period Input Mode (Traps Focus) ---
+                    if app.is_period_input_mode {
+                        match key.code {
+                            _ if app.keymap.binds_in_prompt(Action::Help, key) => {
+                                app.open_help(vec![HelpSection::Prompts])
+                            }
+                            KeyCode::Esc => app.is_period_input_mode = false,
+                            KeyCode::Enter => match app::StatPeriod::parse(&app.period_input) {
+                                Ok(period) => {
+                                    app.is_period_input_mode = false;
+                                    app.set_stat_period(period);
                                 }
+                                Err(e) => app.period_input_error = Some(e),
+                            },
+                            KeyCode::Backspace => {
+                                app.period_input.pop();
+                                app.period_input_error = None;
                             }
-                            KeyCode::Enter => {
+                            KeyCode::Char(c) => {
+                                app.period_input.push(c);
+                                app.period_input_error = None;
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    // Handle Context Menu Keys
+                    if app.context_menu.is_some() {
+                        if key.code == KeyCode::Esc {
+                            app.context_menu = None;
+                            continue;
+                        }
+                        let action = app.action_for_key(key);
+                        let Some(menu) = &mut app.context_menu else {
+                            continue;
+                        };
+                        let last = menu.options.len().saturating_sub(1);
+                        match action {
+                            Some(Action::Help) => app.open_help(vec![HelpSection::ContextMenu]),
+                            Some(Action::Up) => {
+                                menu.selected_index = menu.selected_index.saturating_sub(1);
+                            }
+                            Some(Action::Down) => {
+                                menu.selected_index = (menu.selected_index + 1).min(last);
+                            }
+                            Some(Action::Top) => menu.selected_index = 0,
+                            Some(Action::Bottom) => menu.selected_index = last,
+                            Some(Action::Select) => {
                                 // Process selected context menu option
-                                let option = &menu.options[menu.selected_index];
+                                let option = menu.options[menu.selected_index];
                                 let id = menu.deployment_id.clone();
-                                match option.as_str() {
-                                    "Open in Browser" => {
-                                        if let Some(d) = app.deployments.iter().find(|d| d.id == id)
-                                        {
-                                            let url = format!("https://{}", d.domain);
-                                            let _ = webbrowser::open(&url);
-                                        }
-                                    }
-                                    "Redeploy" => {
-                                        app.confirmation_mode = ConfirmationState::RedeployPending(
-                                            id,
-                                            std::time::Instant::now(),
-                                        );
-                                    }
-                                    "Kill" => {
-                                        if let Some(d) = app.deployments.iter().find(|d| d.id == id)
-                                        {
-                                            if matches!(d.status, network::Status::Building) {
-                                                app.confirmation_mode =
-                                                    ConfirmationState::CancelPending(
-                                                        d.id.clone(),
-                                                        std::time::Instant::now(),
-                                                    );
-                                            }
-                                        }
-                                    }
-                                    _ => {}
-                                }
                                 app.context_menu = None;
+                                run_menu_option(&mut app, option, &id, &cmd_tx, &mut terminal)
+                                    .await;
                             }
                             _ => {}
                         }
@@ -574,114 +708,147 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                     // --- Overlay Modes (Traps Focus) ---
                     if app.show_theme_selector {
-                        match key.code {
-                            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('t') => {
-                                app.show_theme_selector = false
+                        let len = crate::theme::Theme::all().len();
+                        let action = match key.code {
+                            KeyCode::Esc => Some(Action::Themes),
+                            _ => app.action_for_key(key),
+                        };
+                        let step = match action {
+                            Some(Action::Help) => {
+                                app.open_help(vec![HelpSection::Selectors]);
+                                None
                             }
-                            KeyCode::Up | KeyCode::Char('k') => {
-                                let len = crate::theme::Theme::all().len();
-                                let i = match app.theme_list_state.selected() {
-                                    Some(i) => {
-                                        if i == 0 {
-                                            len - 1
-                                        } else {
-                                            i - 1
-                                        }
-                                    }
-                                    None => 0,
-                                };
-                                app.theme_list_state.select(Some(i));
-                                if let Some(theme) = crate::theme::Theme::from_index(i) {
-                                    app.current_theme = theme;
-                                    app.save_config();
-                                }
+                            Some(Action::Select | Action::Themes) => {
+                                app.show_theme_selector = false;
+                                None
                             }
-                            KeyCode::Down | KeyCode::Char('j') => {
-                                let len = crate::theme::Theme::all().len();
-                                let i = match app.theme_list_state.selected() {
-                                    Some(i) => {
-                                        if i >= len - 1 {
-                                            0
-                                        } else {
-                                            i + 1
-                                        }
-                                    }
-                                    None => 0,
-                                };
-                                app.theme_list_state.select(Some(i));
-                                if let Some(theme) = crate::theme::Theme::from_index(i) {
-                                    app.current_theme = theme;
-                                    app.save_config();
-                                }
+                            Some(Action::Transparency) => {
+                                run_action(
+                                    &mut app,
+                                    Action::Transparency,
+                                    None,
+                                    &cmd_tx,
+                                    &mut terminal,
+                                )
+                                .await;
+                                None
                             }
-                            KeyCode::Char('x') => {
-                                app.is_transparent = !app.is_transparent;
+                            Some(Action::Up) => Some(len - 1),
+                            Some(Action::Down) => Some(1),
+                            _ => None,
+                        };
+                        // Themes preview as the selection moves, wrapping around
+                        if let Some(step) = step {
+                            let i = match app.theme_list_state.selected() {
+                                Some(i) => (i + step) % len,
+                                None => 0,
+                            };
+                            app.theme_list_state.select(Some(i));
+                            if let Some(theme) = crate::theme::Theme::from_index(i) {
+                                app.current_theme = theme;
                                 app.save_config();
                             }
-                            _ => {}
                         }
                         continue;
                     }
 
                     if app.show_project_selector {
-                        match key.code {
-                            KeyCode::Esc | KeyCode::Char('p') => app.show_project_selector = false,
-                            KeyCode::Enter => {
+                        let len = app.projects.len();
+                        let action = match key.code {
+                            KeyCode::Esc => Some(Action::Projects),
+                            _ => app.action_for_key(key),
+                        };
+                        let step = match action {
+                            Some(Action::Help) => {
+                                app.open_help(vec![HelpSection::Selectors]);
+                                None
+                            }
+                            Some(Action::Projects) => {
+                                app.show_project_selector = false;
+                                None
+                            }
+                            Some(Action::Transparency) => {
+                                run_action(
+                                    &mut app,
+                                    Action::Transparency,
+                                    None,
+                                    &cmd_tx,
+                                    &mut terminal,
+                                )
+                                .await;
+                                None
+                            }
+                            Some(Action::Select) => {
                                 if let Some(i) = app.project_list_state.selected() {
                                     if i < app.projects.len() {
-                                        let p = &app.projects[i];
-                                        app.current_project = p.name.clone();
-                                        app.current_project_id = Some(p.id.clone());
-                                        // Force switch to "All" time range
-                                        app.stat_period = app::StatPeriod::All;
-
-                                        // CLEAR DATA IMMEDIATELY
-                                        app.deployments.clear();
-                                        app.filtered_deployments.clear();
-                                        app.logs.clear();
-                                        app._list_state.select(None);
-
-                                        app.save_config();
+                                        let p = app.projects[i].clone();
+                                        let id = p.id.clone();
+                                        app.switch_project(Some(p));
                                         // Trigger fetch
                                         let _ = cmd_tx
-                                            .send(network::NetworkCommand::Deployments(Some(
-                                                p.id.clone(),
-                                            )))
+                                            .send(network::NetworkCommand::Deployments(Some(id)))
                                             .await;
                                     }
                                 }
                                 app.show_project_selector = false;
+                                None
                             }
-                            KeyCode::Up | KeyCode::Char('k') => {
-                                let len = app.projects.len();
-                                if len > 0 {
-                                    let i = match app.project_list_state.selected() {
-                                        Some(i) => {
-                                            if i == 0 {
-                                                len - 1
-                                            } else {
-                                                i - 1
-                                            }
-                                        }
-                                        None => 0,
-                                    };
-                                    app.project_list_state.select(Some(i));
+                            Some(Action::Up) => Some(len.saturating_sub(1)),
+                            Some(Action::Down) => Some(1),
+                            _ => None,
+                        };
+                        // Wraps around at either end
+                        if let Some(step) = step.filter(|_| len > 0) {
+                            let i = match app.project_list_state.selected() {
+                                Some(i) => (i + step) % len,
+                                None => 0,
+                            };
+                            app.project_list_state.select(Some(i));
+                        }
+                        continue;
+                    }
+
+                    // --- Breakdown View (Traps Focus) ---
+                    if app.view_mode == ViewMode::Breakdown {
+                        let len = app.breakdown_rows.len();
+                        let action = match key.code {
+                            KeyCode::Esc => Some(Action::Breakdown),
+                            _ => app.action_for_key(key),
+                        };
+                        match action {
+                            Some(Action::Help) => app.open_help(vec![HelpSection::Views]),
+                            Some(Action::Breakdown) => app.view_mode = ViewMode::Deployments,
+                            Some(Action::Quit) => app.should_quit = true,
+                            Some(
+                                action @ (Action::Up | Action::Down | Action::Top | Action::Bottom),
+                            ) => {
+                                move_table_selection(&mut app.breakdown_table_state, len, action);
+                            }
+                            Some(Action::CycleGroup) => {
+                                app.breakdown_group = app.breakdown_group.next();
+                                app.breakdown_table_state.select(Some(0));
+                                app.update_breakdown();
+                            }
+                            Some(Action::SortColumn(n)) => {
+                                if let Some(&column) = BreakdownColumn::all().get(n as usize - 1) {
+                                    app.sort_breakdown_by(column);
                                 }
                             }
-                            KeyCode::Down | KeyCode::Char('j') => {
-                                let len = app.projects.len();
-                                if len > 0 {
-                                    let i = match app.project_list_state.selected() {
-                                        Some(i) => {
-                                            if i >= len - 1 {
-                                                0
-                                            } else {
-                                                i + 1
-                                            }
-                                        }
-                                        None => 0,
-                                    };
-                                    app.project_list_state.select(Some(i));
+                            Some(Action::StatPeriod) => app.set_stat_period(app.stat_period.next()),
+                            // Drill into a branch by filtering the deployment list
+                            Some(Action::Select)
+                                if app.breakdown_group == app::BreakdownGroup::Branch =>
+                            {
+                                if let Some(row) = app
+                                    .breakdown_table_state
+                                    .selected()
+                                    .and_then(|i| app.breakdown_rows.get(i))
+                                {
+                                    app.filter_query = format!("branch:\"{}\"", row.key);
+                                    app.update_filter();
+                                    app._list_state.select(Some(0));
+                                    app.view_mode = ViewMode::Deployments;
+                                    app.active_pane = ActivePane::Deployments;
                                 }
                             }
                             _ => {}
@@ -689,239 +856,165 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         continue;
                     }
 
-                    // Global Clear Error & Confirmation
-                    if key.code == KeyCode::Esc {
-                        if app.error_message.is_some() {
-                            app.error_message = None;
-                        }
-                        app.confirmation_mode = ConfirmationState::None;
-                        continue;
-                    }
-
-                    // --- Main Navigation & Global Commands ---
-                    match key.code {
-                        KeyCode::Right | KeyCode::Char('l') => {
-                            app.active_pane = ActivePane::Logs;
-                        }
-                        KeyCode::Left | KeyCode::Char('h') => {
-                            app.active_pane = ActivePane::Deployments;
-                        }
-                        KeyCode::Up | KeyCode::Char('k') => {
-                            // Check if 'k' is for Kill Confirmation
-                            if key.code == KeyCode::Char('k')
-                                && app.active_pane == ActivePane::Deployments
-                            {
-                                if let Some(i) = app._list_state.selected() {
-                                    if let Some(d) = app.filtered_deployments.get(i) {
-                                        if matches!(d.status, network::Status::Building) {
-                                            // Check confirmation
-                                            if let ConfirmationState::CancelPending(target_id, _) =
-                                                &app.confirmation_mode
-                                            {
-                                                if &d.id == target_id {
-                                                    // CONFIRMED
-                                                    let _ = cmd_tx
-                                                        .send(network::NetworkCommand::Cancel(
-                                                            d.id.clone(),
-                                                        ))
-                                                        .await;
-                                                    app.confirmation_mode = ConfirmationState::None;
-                                                    continue;
-                                                }
-                                            }
-                                            // Pending
-                                            app.confirmation_mode =
-                                                ConfirmationState::CancelPending(
-                                                    d.id.clone(),
-                                                    std::time::Instant::now(),
-                                                );
-                                            continue;
-                                        }
-                                    }
-                                }
+                    // --- Dashboard View (Traps Focus) ---
+                    if app.view_mode == ViewMode::Dashboard {
+                        let len = app.dashboard_rows.len();
+                        let action = match key.code {
+                            KeyCode::Esc => Some(Action::Dashboard),
+                            _ => app.action_for_key(key),
+                        };
+                        match action {
+                            Some(Action::Help) => app.open_help(vec![HelpSection::Views]),
+                            Some(Action::Dashboard) => app.view_mode = ViewMode::Deployments,
+                            Some(Action::Quit) => app.should_quit = true,
+                            Some(
+                                action @ (Action::Up | Action::Down | Action::Top | Action::Bottom),
+                            ) => {
+                                move_table_selection(&mut app.dashboard_table_state, len, action);
                             }
-
-                            match app.active_pane {
-                                ActivePane::Deployments => {
-                                    let i = match app._list_state.selected() {
-                                        Some(i) => {
-                                            if i == 0 {
-                                                app.filtered_deployments.len().saturating_sub(1)
-                                            } else {
-                                                i - 1
-                                            }
-                                        }
-                                        None => 0,
-                                    };
-                                    app._list_state.select(Some(i));
-                                }
-                                ActivePane::Logs => {
-                                    if !app.logs.is_empty() {
-                                        let i = match app.log_list_state.selected() {
-                                            Some(i) => {
-                                                if i == 0 {
-                                                    0
-                                                } else {
-                                                    i - 1
-                                                }
-                                            }
-                                            None => app.logs.len().saturating_sub(1),
-                                        };
-                                        app.log_list_state.select(Some(i));
-                                    }
+                            Some(Action::SortColumn(n)) => {
+                                if let Some(&column) = DashboardColumn::all().get(n as usize - 1) {
+                                    app.sort_dashboard_by(column);
                                 }
                             }
-                            // Reset confirmation if navigating
-                            app.confirmation_mode = ConfirmationState::None;
-                        }
-                        KeyCode::Down | KeyCode::Char('j') => {
-                            match app.active_pane {
-                                ActivePane::Deployments => {
-                                    let i = match app._list_state.selected() {
-                                        Some(i) => {
-                                            if i >= app.filtered_deployments.len().saturating_sub(1)
-                                            {
-                                                0
-                                            } else {
-                                                i + 1
-                                            }
+                            Some(Action::StatPeriod) => app.set_stat_period(app.stat_period.next()),
+                            Some(Action::Select) => {
+                                // Drill into the project's deployments view
+                                let name = app
+                                    .dashboard_table_state
+                                    .selected()
+                                    .and_then(|i| app.dashboard_rows.get(i))
+                                    .map(|r| r.name.clone());
+                                if let Some(name) = name {
+                                    match app.projects.iter().find(|p| p.name == name).cloned() {
+                                        Some(p) => {
+                                            let id = p.id.clone();
+                                            app.switch_project(Some(p));
+                                            app.view_mode = ViewMode::Deployments;
+                                            app.active_pane = ActivePane::Deployments;
+                                            let _ = cmd_tx
+                                                .send(network::NetworkCommand::Deployments(Some(
+                                                    id,
+                                                )))
+                                                .await;
+                                        }
+                                        None => {
+                                            app.error_message =
+                                                Some(format!("Project '{}' not found", name));
                                         }
-                                        None => 0,
-                                    };
-                                    app._list_state.select(Some(i));
-                                }
-                                ActivePane::Logs => {
-                                    if !app.logs.is_empty() {
-                                        let i = match app.log_list_state.selected() {
-                                            Some(i) => {
-                                                if i >= app.logs.len().saturating_sub(1) {
-                                                    app.logs.len().saturating_sub(1)
-                                                } else {
-                                                    i + 1
-                                                }
-                                            }
-                                            None => 0,
-                                        };
-                                        app.log_list_state.select(Some(i));
                                     }
                                 }
                             }
-                            // Reset confirmation if navigating
-                            app.confirmation_mode = ConfirmationState::None;
-                        }
-                        KeyCode::Char('g') => {
-                            // Top
-                            match app.active_pane {
-                                ActivePane::Deployments => app._list_state.select(Some(0)),
-                                ActivePane::Logs => app.log_list_state.select(Some(0)),
-                            }
-                        }
-                        KeyCode::Char('G') => {
-                            // Bottom
-                            match app.active_pane {
-                                ActivePane::Deployments => app
-                                    ._list_state
-                                    .select(Some(app.filtered_deployments.len().saturating_sub(1))),
-                                ActivePane::Logs => app
-                                    .log_list_state
-                                    .select(Some(app.logs.len().saturating_sub(1))),
-                            }
+                            _ => {}
                         }
+                        continue;
+                    }
 
-                        KeyCode::Enter => {
-                            if let Some(i) = app._list_state.selected() {
-                                if i < app.filtered_deployments.len() {
-                                    app.logs.clear();
-                                    app.is_loading_logs = true;
-                                    let id = app.filtered_deployments[i].id.clone();
-                                    let _ = cmd_tx.send(network::NetworkCommand::Logs(id)).await;
-                                    // app.active_pane = ActivePane::Logs; // Optional: switch focus
-                                    app.log_list_state.select(None);
+                    // --- Branch Matrix View (Traps Focus) ---
+                    if app.view_mode == ViewMode::Branches {
+                        let len = app.branch_rows.len();
+                        let action = match key.code {
+                            KeyCode::Esc => Some(Action::Branches),
+                            _ => app.action_for_key(key),
+                        };
+                        match action {
+                            Some(Action::Help) => app.open_help(vec![HelpSection::Views]),
+                            Some(Action::Branches) => app.view_mode = ViewMode::Deployments,
+                            Some(Action::Quit) => app.should_quit = true,
+                            Some(
+                                action @ (Action::Up | Action::Down | Action::Top | Action::Bottom),
+                            ) => {
+                                move_table_selection(&mut app.branch_table_state, len, action);
+                            }
+                            Some(Action::Filter) => app.is_filter_mode = true,
+                            Some(Action::StatPeriod) => app.set_stat_period(app.stat_period.next()),
+                            // Preview URL of the branch's latest deployment
+                            Some(Action::OpenBrowser) => {
+                                if let Some(row) = app
+                                    .branch_table_state
+                                    .selected()
+                                    .and_then(|i| app.branch_rows.get(i))
+                                {
+                                    let url = format!("https://{}", row.latest.domain);
+                                    let _ = webbrowser::open(&url);
                                 }
                             }
-                        }
-
-                        // --- Actions ---
-                        KeyCode::Char('r') => {
-                            if app.active_pane == ActivePane::Deployments {
-                                if let Some(i) = app._list_state.selected() {
-                                    if let Some(d) = app.filtered_deployments.get(i) {
-                                        // Check confirmation
-                                        if let ConfirmationState::RedeployPending(target_id, _) =
-                                            &app.confirmation_mode
-                                        {
-                                            if &d.id == target_id {
-                                                // CONFIRMED
-                                                let _ = cmd_tx
-                                                    .send(network::NetworkCommand::Redeploy(
-                                                        d.id.clone(),
-                                                    ))
-                                                    .await;
-                                                app.confirmation_mode = ConfirmationState::None;
-                                                continue;
-                                            }
-                                        }
-                                        // Pending
-                                        app.confirmation_mode = ConfirmationState::RedeployPending(
-                                            d.id.clone(),
-                                            std::time::Instant::now(),
-                                        );
-                                    }
+                            // Open the branch's history in the deployment list
+                            Some(Action::Select) => {
+                                if let Some(row) = app
+                                    .branch_table_state
+                                    .selected()
+                                    .and_then(|i| app.branch_rows.get(i))
+                                {
+                                    app.filter_query = app.branch_history_query(row);
+                                    app.update_filter();
+                                    app._list_state.select(Some(0));
+                                    app.view_mode = ViewMode::Deployments;
+                                    app.active_pane = ActivePane::Deployments;
                                 }
                             }
+                            _ => {}
                         }
+                        continue;
+                    }
 
-                        // --- Command Mode Logic ---
-                        KeyCode::Char(' ') => {
-                            app.show_legend = !app.show_legend;
-                        }
-
-                        // --- Global Action Keys (Ungated) ---
-                        KeyCode::Char('q') => app.should_quit = true,
-                        KeyCode::Char('t') => {
-                            app.show_theme_selector = true;
-                            app.theme_list_state
-                                .select(Some(app.current_theme.as_index()));
-                            app.show_legend = false;
-                        }
-                        KeyCode::Char('s') => {
-                            app.stat_period = app.stat_period.next();
-                            app.save_config();
-
-                            // Re-apply filter with new period
-                            let current_id = app.get_selected_deployment_id();
-                            app.update_filter();
-                            app.select_deployment_by_id(current_id);
-                        }
-                        KeyCode::Char('p') => {
-                            app.show_project_selector = true;
-                            if app.projects.is_empty() {
-                                let _ = cmd_tx.send(network::NetworkCommand::Projects).await;
+                    // --- Compare View (Traps Focus) ---
+                    if app.view_mode == ViewMode::Compare {
+                        let action = match key.code {
+                            KeyCode::Esc => Some(Action::Compare),
+                            _ => app.action_for_key(key),
+                        };
+                        match action {
+                            Some(Action::Help) => app.open_help(vec![HelpSection::Views]),
+                            Some(Action::Compare) => {
+                                app.view_mode = ViewMode::Deployments;
+                                app.comparison = None;
                             }
-                            app.project_list_state.select(Some(0));
-                            app.show_legend = false;
-                        }
-                        KeyCode::Char('/') => {
-                            app.is_filter_mode = true;
-                            app.active_pane = ActivePane::Deployments;
-                            // Don't clear query, allow refinement. Esc clears it.
-                            app.show_legend = false;
-                        }
-                        KeyCode::Char('o') => {
-                            if let Some(i) = app._list_state.selected() {
-                                if let Some(d) = app.filtered_deployments.get(i) {
-                                    let url = format!("https://{}", d.domain);
-                                    let _ = webbrowser::open(&url);
-                                }
+                            Some(Action::Quit) => app.should_quit = true,
+                            Some(Action::Up) => {
+                                app.compare_scroll = app.compare_scroll.saturating_sub(1);
+                            }
+                            Some(Action::Down) => {
+                                app.compare_scroll = app.compare_scroll.saturating_add(1);
+                            }
+                            Some(Action::PageUp) => {
+                                app.compare_scroll = app.compare_scroll.saturating_sub(20);
+                            }
+                            Some(Action::PageDown) => {
+                                app.compare_scroll = app.compare_scroll.saturating_add(20);
                             }
-                            app.show_legend = false;
+                            Some(Action::Top) => app.compare_scroll = 0,
+                            // Clamped to the content when drawing
+                            Some(Action::Bottom) => app.compare_scroll = u16::MAX,
+                            _ => {}
                         }
-                        KeyCode::Char('m') => {
-                            app.enable_mouse = !app.enable_mouse;
-                            app.save_config();
+                        continue;
+                    }
+
+                    // Global Clear Error & Confirmation
+                    if key.code == KeyCode::Esc {
+                        if app.error_message.is_some() {
+                            app.error_message = None;
+                        } else if app.log_range_anchor.is_some() {
+                            app.log_range_anchor = None;
+                        } else if app.mark_anchor.is_some() {
+                            app.mark_anchor = None;
+                        } else if app.compare_base.is_some() {
+                            app.compare_base = None;
+                        } else if app.show_log_fullscreen && !app.log_search_query.is_empty() {
+                            app.clear_log_search();
+                        } else if app.show_log_fullscreen {
+                            app.show_log_fullscreen = false;
+                            app.active_pane = ActivePane::Deployments;
                         }
-                        _ => {}
-                    } // End match key
+                        app.confirmation_mode = ConfirmationState::None;
+                        continue;
+                    }
+
+                    // --- Main Navigation & Global Commands ---
+                    if let Some(action) = app.action_for_key(key) {
+                        run_action(&mut app, action, None, &cmd_tx, &mut terminal).await;
+                    }
                 } // End Event::Key
                 _ => {}
             }
@@ -929,26 +1022,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         // Auto-fetch logs on selection change logic (Debounce)
         if let Some(i) = app._list_state.selected() {
-            if i != last_selected_index && i < app.filtered_deployments.len() {
+            if i != last_selected_index && i < app.list_rows.len() {
                 last_selected_index = i;
-                app.logs.clear();
-                app.is_loading_logs = true;
+                app.clear_logs();
+                // Group headers have no logs
+                app.is_loading_logs = app.deployment_at(i).is_some();
                 // Set debounce timer
-                log_debounce_timer = Some(time::Instant::now() + Duration::from_millis(400));
+                log_debounce_timer = app
+                    .is_loading_logs
+                    .then(|| time::Instant::now() + Duration::from_millis(400));
             }
         }
 
         // Handle Debounce Timer
         if let Some(deadline) = log_debounce_timer {
             if time::Instant::now() >= deadline {
-                if let Some(i) = app._list_state.selected() {
-                    if i < app.filtered_deployments.len() {
-                        let id = app.filtered_deployments[i].id.clone();
-                        let _ = cmd_tx
-                            .send(network::NetworkCommand::StartStream(id.clone()))
-                            .await;
-                        let _ = cmd_tx.send(network::NetworkCommand::Logs(id)).await;
-                    }
+                if let Some(id) = app.get_selected_deployment_id() {
+                    let _ = cmd_tx
+                        .send(network::NetworkCommand::StartStream(id.clone()))
+                        .await;
+                    let _ = cmd_tx.send(network::NetworkCommand::Logs(id)).await;
                 }
                 log_debounce_timer = None;
             }
@@ -971,16 +1064,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         // This placement is a best guess given the provided snippet does not contain terminal.draw().
         app.update_stats();
 
+        // Scrolling away from the newest line pauses follow mode
+        app.update_log_follow();
+
         // Handle Network Events
         while let Ok(event) = rx.try_recv() {
             match event {
                 NetworkEvent::Deployments(deployments) => {
                     app.error_message = None;
 
-                    // Capture current selection
-                    let current_id = app.get_selected_deployment_id();
+                    // Capture current selection (a deployment or group header)
+                    let current_id = app.selected_row_id();
 
                     app.deployments = deployments;
+                    app.update_flaky_commits();
 
                     // Re-apply filter on new data to ensure list consistency
                     app.update_filter();
@@ -990,27 +1087,39 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
                 NetworkEvent::Projects(projects) => {
                     app.projects = projects;
+                    if app.view_mode == ViewMode::Dashboard {
+                        app.update_dashboard();
+                        fetch_dashboard(&mut app, &cmd_tx).await;
+                    }
+                }
+                NetworkEvent::ProjectDeployments(id, deployments) => {
+                    app.dashboard_deployments.insert(id, deployments);
+                    app.update_dashboard();
                 }
                 NetworkEvent::Logs(id, logs) => {
                     // Check if the log belongs to currently selected item
-                    if let Some(i) = app._list_state.selected() {
-                        if i < app.filtered_deployments.len()
-                            && app.filtered_deployments[i].id == id
+                    if app.get_selected_deployment_id().as_deref() == Some(id.as_str()) {
+                        app.set_logs(logs);
+                        app.set_log_follow(app.is_selected_building());
+                        // Failed builds open at the cause of the failure
+                        if app
+                            .selected_deployment()
+                            .is_some_and(|d| matches!(d.status, network::Status::Error))
                         {
-                            app.logs = logs;
-                            app.is_loading_logs = false;
+                            app.jump_to_first_error();
                         }
+                        app.is_loading_logs = false;
                     }
                 }
                 NetworkEvent::LogChunk(id, new_lines) => {
-                    if let Some(i) = app._list_state.selected() {
-                        if i < app.filtered_deployments.len()
-                            && app.filtered_deployments[i].id == id
-                        {
-                            // Deduplication is now handled in network.rs
-                            app.logs.extend(new_lines);
-                            // Auto-scroll logic could go here
-                        }
+                    if app.get_selected_deployment_id().as_deref() == Some(id.as_str()) {
+                        // Deduplication is now handled in network.rs
+                        app.append_logs(new_lines);
+                    }
+                }
+                NetworkEvent::CompareLogs(id, logs) => {
+                    if let Some(comparison) = &mut app.comparison {
+                        comparison.set_logs(&id, logs);
                     }
                 }
                 NetworkEvent::Info(msg) => {
@@ -1021,6 +1130,69 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     app.error_message = Some(msg);
                     app.is_loading_logs = false;
                 }
+                NetworkEvent::BatchProgress {
+                    action,
+                    done,
+                    total,
+                    id,
+                    result,
+                } => {
+                    let colors = app.current_theme.get_colors();
+                    let short_id = app.short_id_of(&id);
+                    let (msg, color) = match result {
+                        Ok(()) => {
+                            app.marked.remove(&id);
+                            (
+                                format!(
+                                    "{} {}/{}: {} ✓",
+                                    action.display_text(),
+                                    done,
+                                    total,
+                                    short_id
+                                ),
+                                colors.status_success,
+                            )
+                        }
+                        Err(e) => (
+                            format!(
+                                "{} {}/{}: {} ✗ {}",
+                                action.display_text(),
+                                done,
+                                total,
+                                short_id,
+                                e
+                            ),
+                            colors.status_error,
+                        ),
+                    };
+                    app.toast_message = Some((msg, color, std::time::Instant::now()));
+                }
+                NetworkEvent::BatchDone {
+                    action,
+                    total,
+                    failures,
+                } => {
+                    if failures.is_empty() {
+                        app.toast_message = Some((
+                            format!("{} {} deployment(s)", action.past_tense(), total),
+                            app.current_theme.get_colors().status_success,
+                            std::time::Instant::now(),
+                        ));
+                    } else {
+                        // Failed items stay marked so the batch can be retried
+                        let details: Vec<String> = failures
+                            .iter()
+                            .map(|(id, e)| format!("{}: {}", app.short_id_of(id), e))
+                            .collect();
+                        app.error_message = Some(format!(
+                            "{} {} of {} deployments. Failed:\n{}",
+                            action.past_tense(),
+                            total - failures.len(),
+                            total,
+                            details.join("\n")
+                        ));
+                    }
+                }
             }
         }
 
@@ -1033,3 +1205,560 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Handled by TerminalGuard
     Ok(())
 }
+
+/// Runs `action` on the current selection, or on the deployment `target` for context
+/// menu entries. Keys, the command palette and the context menu all come through here.
+async fn run_action(
+    app: &mut App,
+    action: Action,
+    target: Option<&str>,
+    cmd_tx: &mpsc::Sender<network::NetworkCommand>,
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+) {
+    match action {
+        // --- Full-screen Log Viewer ---
+        Action::LogViewer => {
+            app.show_log_fullscreen = !app.show_log_fullscreen;
+            app.active_pane = if app.show_log_fullscreen {
+                ActivePane::Logs
+            } else {
+                ActivePane::Deployments
+            };
+            app.show_legend = false;
+        }
+        Action::LogSearch => {
+            app.is_log_search_mode = true;
+            app.set_log_search(String::new());
+        }
+        Action::NextHit => app.next_log_hit(true),
+        Action::PrevHit => app.next_log_hit(false),
+        Action::JumpToLine => {
+            app.is_log_jump_mode = true;
+            app.log_jump_input.clear();
+        }
+        Action::FocusLogs if Action::FocusLogs.available(app) => {
+            app.active_pane = ActivePane::Logs;
+            // Compact mode has no room for a side pane
+            if app.is_compact {
+                app.show_log_fullscreen = true;
+            }
+        }
+        Action::FocusLogs => {}
+        Action::FocusList => {
+            app.active_pane = ActivePane::Deployments;
+            app.show_log_fullscreen = false;
+        }
+        Action::Up => {
+            match app.active_pane {
+                ActivePane::Deployments => {
+                    let i = match app._list_state.selected() {
+                        Some(i) => {
+                            if i == 0 {
+                                app.list_rows.len().saturating_sub(1)
+                            } else {
+                                i - 1
+                            }
+                        }
+                        None => 0,
+                    };
+                    app._list_state.select(Some(i));
+                }
+                ActivePane::Logs => {
+                    if !app.log_rows.is_empty() {
+                        let i = match app.log_list_state.selected() {
+                            Some(i) => {
+                                if i == 0 {
+                                    0
+                                } else {
+                                    i - 1
+                                }
+                            }
+                            None => app.log_rows.len().saturating_sub(1),
+                        };
+                        app.log_list_state.select(Some(i));
+                    }
+                }
+            }
+            // Reset confirmation if navigating
+            app.confirmation_mode = ConfirmationState::None;
+        }
+        Action::Down => {
+            match app.active_pane {
+                ActivePane::Deployments => {
+                    let i = match app._list_state.selected() {
+                        Some(i) => {
+                            if i >= app.list_rows.len().saturating_sub(1) {
+                                0
+                            } else {
+                                i + 1
+                            }
+                        }
+                        None => 0,
+                    };
+                    app._list_state.select(Some(i));
+                }
+                ActivePane::Logs => {
+                    if !app.log_rows.is_empty() {
+                        let i = match app.log_list_state.selected() {
+                            Some(i) => {
+                                if i >= app.log_rows.len().saturating_sub(1) {
+                                    app.log_rows.len().saturating_sub(1)
+                                } else {
+                                    i + 1
+                                }
+                            }
+                            None => 0,
+                        };
+                        app.log_list_state.select(Some(i));
+                    }
+                }
+            }
+            // Reset confirmation if navigating
+            app.confirmation_mode = ConfirmationState::None;
+        }
+        Action::Top => match app.active_pane {
+            ActivePane::Deployments => app._list_state.select(Some(0)),
+            ActivePane::Logs => app.log_list_state.select(Some(0)),
+        },
+        Action::Bottom => match app.active_pane {
+            ActivePane::Deployments => app
+                ._list_state
+                .select(Some(app.list_rows.len().saturating_sub(1))),
+            ActivePane::Logs if app.is_selected_building() => app.set_log_follow(true),
+            ActivePane::Logs => app
+                .log_list_state
+                .select(Some(app.log_rows.len().saturating_sub(1))),
+        },
+        Action::Follow => app.set_log_follow(!app.log_follow),
+        Action::Select => {
+            if app
+                .group_at(app._list_state.selected().unwrap_or(0))
+                .is_some()
+            {
+                app.toggle_selected_group();
+            } else if let Some(d) = app.selected_deployment() {
+                let id = d.id.clone();
+                app.clear_logs();
+                app.is_loading_logs = true;
+                let _ = cmd_tx.send(network::NetworkCommand::Logs(id)).await;
+                app.log_list_state.select(None);
+                if app.is_compact {
+                    app.active_pane = ActivePane::Logs;
+                    app.show_log_fullscreen = true;
+                }
+            }
+        }
+
+        // --- Actions ---
+        // Marked deployments are redeployed together, unless a menu names one
+        Action::Redeploy if target.is_none() && app.mark_count() > 0 => {
+            if let Some(ids) = app.confirm_batch(BatchAction::Redeploy) {
+                let _ = cmd_tx
+                    .send(network::NetworkCommand::Batch(BatchAction::Redeploy, ids))
+                    .await;
+            }
+        }
+        Action::Redeploy => {
+            if let Some(id) = app.target_deployment(target).map(|d| d.id.clone()) {
+                // A second press on the same deployment confirms
+                if matches!(&app.confirmation_mode,
+                    ConfirmationState::RedeployPending(target_id, _) if *target_id == id)
+                {
+                    let _ = cmd_tx.send(network::NetworkCommand::Redeploy(id)).await;
+                    app.confirmation_mode = ConfirmationState::None;
+                } else {
+                    app.confirmation_mode =
+                        ConfirmationState::RedeployPending(id, std::time::Instant::now());
+                }
+            }
+        }
+        // Marked builds are canceled together
+        Action::Cancel
+            if target.is_none() && !app.batch_targets(BatchAction::Cancel).is_empty() =>
+        {
+            if let Some(ids) = app.confirm_batch(BatchAction::Cancel) {
+                let _ = cmd_tx
+                    .send(network::NetworkCommand::Batch(BatchAction::Cancel, ids))
+                    .await;
+            }
+        }
+        Action::Cancel => {
+            let building = app
+                .target_deployment(target)
+                .filter(|d| matches!(d.status, network::Status::Building))
+                .map(|d| d.id.clone());
+            if let Some(id) = building {
+                if matches!(&app.confirmation_mode,
+                    ConfirmationState::CancelPending(target_id, _) if *target_id == id)
+                {
+                    let _ = cmd_tx.send(network::NetworkCommand::Cancel(id)).await;
+                    app.confirmation_mode = ConfirmationState::None;
+                } else {
+                    app.confirmation_mode =
+                        ConfirmationState::CancelPending(id, std::time::Instant::now());
+                }
+            }
+        }
+
+        // --- Marks ---
+        Action::Mark => {
+            app.toggle_mark();
+            if let Some(i) = app._list_state.selected() {
+                if i + 1 < app.list_rows.len() {
+                    app._list_state.select(Some(i + 1));
+                }
+            }
+            app.confirmation_mode = ConfirmationState::None;
+        }
+        Action::MarkRange => {
+            app.toggle_mark_range();
+            app.confirmation_mode = ConfirmationState::None;
+        }
+        Action::MarkQueued => {
+            let count = app.mark_queued_branch_builds();
+            let colors = app.current_theme.get_colors();
+            let (msg, color) = match count {
+                0 => (
+                    "No queued preview builds on this branch".to_string(),
+                    colors.text_dim,
+                ),
+                n => (
+                    format!(
+                        "Marked {} queued preview build(s), press '{}' to cancel",
+                        n,
+                        app.keymap.key(Action::Cancel)
+                    ),
+                    colors.status_building,
+                ),
+            };
+            app.toast_message = Some((msg, color, std::time::Instant::now()));
+            app.confirmation_mode = ConfirmationState::None;
+        }
+        Action::ClearMarks => {
+            app.clear_marks();
+            app.confirmation_mode = ConfirmationState::None;
+        }
+
+        // --- Command Mode Logic ---
+        Action::Legend => {
+            app.show_legend = !app.show_legend;
+        }
+        Action::Palette => app.open_command_palette(),
+        Action::Help => {
+            let sections = app
+                .active_scopes()
+                .into_iter()
+                .map(|scope| match scope {
+                    Scope::Global => HelpSection::General,
+                    Scope::List => HelpSection::Deployments,
+                    Scope::Logs => HelpSection::Logs,
+                    Scope::LogViewer => HelpSection::LogViewer,
+                    Scope::Overlay => HelpSection::Views,
+                })
+                .collect();
+            app.open_help(sections);
+        }
+
+        // --- Global Action Keys (Ungated) ---
+        Action::Quit => app.should_quit = true,
+        Action::Themes => {
+            app.show_theme_selector = true;
+            app.theme_list_state
+                .select(Some(app.current_theme.as_index()));
+            app.show_legend = false;
+        }
+        Action::StatPeriod => app.set_stat_period(app.stat_period.next()),
+        Action::CustomPeriod => {
+            app.is_period_input_mode = true;
+            app.period_input = app.stat_period.config_string();
+            app.period_input_error = None;
+            app.show_legend = false;
+        }
+        Action::Breakdown => {
+            app.view_mode = ViewMode::Breakdown;
+            app.update_breakdown();
+            app.show_legend = false;
+        }
+        Action::Compare => {
+            if let Some((a, b)) = app.start_compare() {
+                for id in [a, b] {
+                    let _ = cmd_tx.send(network::NetworkCommand::CompareLogs(id)).await;
+                }
+            } else {
+                let msg = match &app.compare_base {
+                    Some(id) => format!(
+                        "Comparing from {}: select another deployment and press {}",
+                        app.short_id_of(id),
+                        app.keymap.key(Action::Compare)
+                    ),
+                    None => "Compare canceled".to_string(),
+                };
+                app.toast_message = Some((
+                    msg,
+                    app.current_theme.get_colors().accent_primary,
+                    std::time::Instant::now(),
+                ));
+            }
+            app.show_legend = false;
+        }
+        Action::Branches => {
+            app.view_mode = ViewMode::Branches;
+            app.update_branches();
+            app.show_legend = false;
+        }
+        Action::Dashboard => {
+            app.view_mode = ViewMode::Dashboard;
+            app.update_dashboard();
+            app.show_legend = false;
+            fetch_dashboard(app, cmd_tx).await;
+        }
+        Action::Projects => {
+            app.show_project_selector = true;
+            if app.projects.is_empty() {
+                let _ = cmd_tx.send(network::NetworkCommand::Projects).await;
+            }
+            app.project_list_state.select(Some(0));
+            app.show_legend = false;
+        }
+        Action::Filter => {
+            app.is_filter_mode = true;
+            app.active_pane = ActivePane::Deployments;
+            // Don't clear query, allow refinement. Esc clears it.
+            app.show_legend = false;
+        }
+        Action::OpenBrowser => {
+            if let Some(d) = app.target_deployment(target) {
+                let url = format!("https://{}", d.domain);
+                let _ = webbrowser::open(&url);
+            }
+            app.show_legend = false;
+        }
+        // --- Git Provider Links ---
+        Action::OpenCommit | Action::OpenBranch | Action::OpenPullRequest => {
+            let link = match action {
+                Action::OpenCommit => GitLink::Commit,
+                Action::OpenBranch => GitLink::Branch,
+                _ => GitLink::PullRequest,
+            };
+            let colors = app.current_theme.get_colors();
+            let result = app.selected_git_url(link).and_then(|url| {
+                webbrowser::open(&url)
+                    .map(|_| url)
+                    .map_err(|e| format!("Failed to open browser: {}", e))
+            });
+            app.toast_message = Some(match result {
+                Ok(url) => (
+                    format!("Opened {}", url),
+                    colors.status_success,
+                    std::time::Instant::now(),
+                ),
+                Err(e) => (e, colors.status_error, std::time::Instant::now()),
+            });
+            app.show_legend = false;
+        }
+        Action::Layout => {
+            app.cycle_layout();
+            app.toast_message = Some((
+                format!("Layout: {}", app.layout().name),
+                app.current_theme.get_colors().accent_primary,
+                std::time::Instant::now(),
+            ));
+        }
+        // --- Log Filters & Folding ---
+        Action::ProblemsOnly => {
+            app.toggle_log_problems_only();
+            let msg = if app.log_problems_only {
+                "Logs: warnings & errors only"
+            } else {
+                "Logs: all levels"
+            };
+            app.toast_message = Some((
+                msg.to_string(),
+                app.current_theme.get_colors().accent_primary,
+                std::time::Instant::now(),
+            ));
+        }
+        Action::InstallNoise => {
+            app.toggle_install_noise();
+            let msg = if app.hide_install_noise {
+                "Logs: install output hidden"
+            } else {
+                "Logs: install output shown"
+            };
+            app.toast_message = Some((
+                msg.to_string(),
+                app.current_theme.get_colors().accent_primary,
+                std::time::Instant::now(),
+            ));
+        }
+        Action::NativeColors => {
+            app.native_log_colors = !app.native_log_colors;
+            app.save_config();
+            let msg = if app.native_log_colors {
+                "Logs: native colors"
+            } else {
+                "Logs: Polymer highlighting"
+            };
+            app.toast_message = Some((
+                msg.to_string(),
+                app.current_theme.get_colors().accent_primary,
+                std::time::Instant::now(),
+            ));
+        }
+        Action::JumpToError => {
+            if app.jump_to_first_error() {
+                app.active_pane = ActivePane::Logs;
+            } else {
+                app.toast_message = Some((
+                    "No errors found in log".to_string(),
+                    app.current_theme.get_colors().text_dim,
+                    std::time::Instant::now(),
+                ));
+            }
+        }
+        Action::OpenInEditor => {
+            let result = app.selected_file_ref().and_then(|(path, file_ref)| {
+                editor::open(terminal, &path, file_ref.line, file_ref.column)
+            });
+            if let Err(e) = result {
+                app.toast_message = Some((
+                    e,
+                    app.current_theme.get_colors().status_error,
+                    std::time::Instant::now(),
+                ));
+            }
+        }
+        // --- Export & Copy ---
+        Action::LogRange => {
+            app.log_range_anchor = match app.log_range_anchor {
+                Some(_) => None,
+                None => app.selected_log_line(),
+            };
+        }
+        Action::ExportLogs | Action::ExportLogsWithTimestamps => {
+            let colors = app.current_theme.get_colors();
+            let with_timestamps = action == Action::ExportLogsWithTimestamps;
+            let (msg, color) = match app.export_logs(with_timestamps) {
+                Ok(path) => (
+                    format!("Saved log to {}", path.display()),
+                    colors.status_success,
+                ),
+                Err(e) => (e, colors.status_error),
+            };
+            app.toast_message = Some((msg, color, std::time::Instant::now()));
+        }
+        Action::CopyUrl | Action::CopyId | Action::CopyCommitMsg | Action::CopyLogLines => {
+            app.copy_item(action, target);
+        }
+        Action::CopyMenu => {
+            if let Some(d) = app.selected_deployment() {
+                let id = d.id.clone();
+                let area = if app.active_pane == ActivePane::Logs {
+                    app.logs_area
+                } else {
+                    app.deployments_area
+                };
+                let mut options = vec![Action::CopyUrl, Action::CopyId, Action::CopyCommitMsg];
+                if app.active_pane == ActivePane::Logs {
+                    options.insert(0, Action::CopyLogLines);
+                }
+                app.context_menu = Some(ContextMenu {
+                    position: (area.x + 2, area.y + 1),
+                    deployment_id: id,
+                    selected_index: 0,
+                    options,
+                });
+            }
+        }
+        Action::TimeMode => {
+            app.log_time_mode = app.log_time_mode.next();
+            app.toast_message = Some((
+                format!("Log timestamps: {}", app.log_time_mode.display_text()),
+                app.current_theme.get_colors().accent_primary,
+                std::time::Instant::now(),
+            ));
+        }
+        Action::FoldSection => app.toggle_log_section(),
+        Action::FoldAllSections => app.toggle_all_log_sections(),
+        // --- List Order & Grouping ---
+        Action::FoldGroup => app.toggle_selected_group(),
+        Action::FoldAllGroups => app.toggle_all_groups(),
+        Action::CycleSort => {
+            app.cycle_sort_mode();
+            app.toast_message = Some((
+                format!("Sort: {}", app.sort_mode.display_text()),
+                app.current_theme.get_colors().accent_primary,
+                std::time::Instant::now(),
+            ));
+        }
+        Action::GroupTree => {
+            app.toggle_group_tree();
+            let msg = if app.group_tree {
+                "List: grouped by project → branch"
+            } else {
+                "List: flat"
+            };
+            app.toast_message = Some((
+                msg.to_string(),
+                app.current_theme.get_colors().accent_primary,
+                std::time::Instant::now(),
+            ));
+        }
+        Action::Mouse => {
+            app.enable_mouse = !app.enable_mouse;
+            app.save_config();
+        }
+        Action::Transparency => {
+            app.is_transparent = !app.is_transparent;
+            app.save_config();
+        }
+        // Only bound inside the views, which handle them themselves
+        Action::SortColumn(_) | Action::CycleGroup | Action::PageUp | Action::PageDown => {}
+    }
+}
+
+/// Moves a table view's selection for one of the cursor actions
+fn move_table_selection(state: &mut TableState, len: usize, action: Action) {
+    let last = len.saturating_sub(1);
+    let i = match (action, state.selected()) {
+        (Action::Up, Some(i)) => i.saturating_sub(1),
+        (Action::Down, Some(i)) => (i + 1).min(last),
+        (Action::Bottom, _) => last,
+        _ => 0,
+    };
+    state.select(Some(i));
+}
+
+/// Fetches every project's history for the dashboard, or the project list first.
+/// The current project and its deployment list are left alone.
+async fn fetch_dashboard(app: &mut App, cmd_tx: &mpsc::Sender<network::NetworkCommand>) {
+    let command = if app.projects.is_empty() {
+        network::NetworkCommand::Projects
+    } else {
+        app.dashboard_period = Some(app.stat_period);
+        network::NetworkCommand::ProjectDeployments(
+            app.projects.iter().map(|p| p.id.clone()).collect(),
+            app.stat_period.bounds().map(|(start, _)| start),
+        )
+    };
+    let _ = cmd_tx.send(command).await;
+}
+
+/// Runs a context menu entry on the deployment the menu was opened on
+async fn run_menu_option(
+    app: &mut App,
+    action: Action,
+    deployment_id: &str,
+    cmd_tx: &mpsc::Sender<network::NetworkCommand>,
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+) {
+    if app.target_deployment(Some(deployment_id)).is_none() {
+        app.toast_message = Some((
+            "Deployment is no longer loaded".to_string(),
+            app.current_theme.get_colors().status_error,
+            std::time::Instant::now(),
+        ));
+        return;
+    }
+    run_action(app, action, Some(deployment_id), cmd_tx, terminal).await;
+}
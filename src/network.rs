@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use tokio::sync::mpsc;
 
+use crate::logs::{LogEventType, LogLine};
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Status {
     Ready,
@@ -26,21 +28,133 @@ pub struct Deployment {
     pub creator: String,
     pub target: String,
     pub short_id: String,
+    pub commit_sha: Option<String>,
+    pub provider: Option<GitProvider>,
+    /// Owner of the repository: GitHub org, GitLab namespace or Bitbucket workspace
+    pub org: Option<String>,
+    /// Commit author as known to the git provider (`creator` is the Vercel user)
+    pub commit_author: Option<String>,
+    /// Pull request (GitLab: merge request) the deployment was built for
+    pub pr_number: Option<u64>,
+}
+
+/// Git host a deployment was built from
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum GitProvider {
+    Github,
+    Gitlab,
+    Bitbucket,
+}
+
+impl GitProvider {
+    pub fn display_text(&self) -> &'static str {
+        match self {
+            GitProvider::Github => "GitHub",
+            GitProvider::Gitlab => "GitLab",
+            GitProvider::Bitbucket => "Bitbucket",
+        }
+    }
+}
+
+impl Deployment {
+    /// Web URL of the repository. GitLab may be self-managed, so its URL comes from the
+    /// project's link; Bitbucket links only work on Bitbucket Cloud.
+    pub fn repo_url(&self, link: Option<&ProjectLink>) -> Result<String, String> {
+        let provider = self
+            .provider
+            .ok_or("No git provider info for this deployment")?;
+        let org = self
+            .org
+            .as_ref()
+            .ok_or("No repository info for this deployment");
+        match provider {
+            GitProvider::Github => Ok(format!("https://github.com/{}/{}", org?, self.repo)),
+            GitProvider::Gitlab => link
+                .and_then(|l| l.project_url.as_deref())
+                .map(|url| url.trim_end_matches('/').to_string())
+                .ok_or("No GitLab project URL for this deployment"),
+            GitProvider::Bitbucket => match link {
+                Some(l) if l.workspace_uuid.is_some() => {
+                    Ok(format!("https://bitbucket.org/{}/{}", org?, self.repo))
+                }
+                _ => Err("Only Bitbucket Cloud repositories can be opened"),
+            },
+        }
+        .map_err(str::to_string)
+    }
+
+    pub fn commit_url(&self, repo_url: &str) -> Option<String> {
+        let sha = self.commit_sha.as_ref()?;
+        let path = match self.provider? {
+            GitProvider::Github => "commit",
+            GitProvider::Gitlab => "-/commit",
+            GitProvider::Bitbucket => "commits",
+        };
+        Some(format!("{}/{}/{}", repo_url, path, sha))
+    }
+
+    pub fn branch_url(&self, repo_url: &str) -> Option<String> {
+        let path = match self.provider? {
+            GitProvider::Github => "tree",
+            GitProvider::Gitlab => "-/tree",
+            GitProvider::Bitbucket => "branch",
+        };
+        Some(format!("{}/{}/{}", repo_url, path, self.branch))
+    }
+
+    pub fn pr_url(&self, repo_url: &str) -> Option<String> {
+        let number = self.pr_number?;
+        let path = match self.provider? {
+            GitProvider::Github => "pull",
+            GitProvider::Gitlab => "-/merge_requests",
+            GitProvider::Bitbucket => "pull-requests",
+        };
+        Some(format!("{}/{}/{}", repo_url, path, number))
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Project {
     pub id: String,
     pub name: String,
+    /// Connected git repository
+    #[serde(default)]
+    pub link: Option<ProjectLink>,
+}
+
+/// The parts of a project's git link needed to build repository URLs
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProjectLink {
+    /// GitLab: web URL of the project on whichever host it lives
+    pub project_url: Option<String>,
+    /// Bitbucket Cloud workspace; Bitbucket Server repositories have none
+    pub workspace_uuid: Option<String>,
 }
 
 pub enum NetworkEvent {
     Deployments(Vec<Deployment>),
     Projects(Vec<Project>),
-    Logs(String, Vec<String>),     // DeploymentID, Logs (Type: Full)
-    LogChunk(String, Vec<String>), // DeploymentID, Logs (Type: Chunk)
+    Logs(String, Vec<LogLine>),        // DeploymentID, Logs (Type: Full)
+    LogChunk(String, Vec<LogLine>),    // DeploymentID, Logs (Type: Chunk)
+    CompareLogs(String, Vec<LogLine>), // DeploymentID, Logs for the comparison view
+    ProjectDeployments(String, Vec<Deployment>), // ProjectID, Deployments for the dashboard
     Info(String),
     Error(String),
+    /// One item of a batch finished
+    BatchProgress {
+        action: BatchAction,
+        done: usize,
+        total: usize,
+        id: String,
+        result: Result<(), String>,
+    },
+    /// The whole batch finished; `failures` holds (deployment ID, error)
+    BatchDone {
+        action: BatchAction,
+        total: usize,
+        failures: Vec<(String, String)>,
+    },
 }
 
 // --- Vercel API Types ---
@@ -63,21 +177,120 @@ pub struct Creator {
     pub username: String,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Git metadata Vercel attaches to a deployment. Each provider uses its own keys.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct Meta {
-    #[serde(rename = "githubCommitMessage")]
     pub github_commit_message: Option<String>,
-    #[serde(rename = "githubRepo")]
     pub github_repo: Option<String>,
-    #[serde(rename = "githubCommitRef")]
     pub github_commit_ref: Option<String>,
+    pub github_commit_sha: Option<String>,
+    pub github_commit_author_name: Option<String>,
+    pub github_commit_author_login: Option<String>,
+    pub github_org: Option<String>,
+    pub github_commit_org: Option<String>,
+    pub github_pr_id: Option<String>,
+
+    pub gitlab_commit_message: Option<String>,
+    pub gitlab_project_name: Option<String>,
+    pub gitlab_project_namespace: Option<String>,
+    pub gitlab_commit_ref: Option<String>,
+    pub gitlab_commit_sha: Option<String>,
+    pub gitlab_commit_author_name: Option<String>,
+    pub gitlab_commit_author_login: Option<String>,
+    pub gitlab_merge_request_iid: Option<String>,
+
+    pub bitbucket_commit_message: Option<String>,
+    pub bitbucket_repo_slug: Option<String>,
+    pub bitbucket_repo_name: Option<String>,
+    pub bitbucket_repo_owner: Option<String>,
+    pub bitbucket_commit_ref: Option<String>,
+    pub bitbucket_commit_sha: Option<String>,
+    pub bitbucket_commit_author_name: Option<String>,
+    pub bitbucket_pr_id: Option<String>,
+}
+
+/// Provider-neutral view of `Meta`
+struct GitMeta {
+    provider: GitProvider,
+    message: Option<String>,
+    repo: Option<String>,
+    org: Option<String>,
+    branch: Option<String>,
+    sha: Option<String>,
+    author: Option<String>,
+    pr_number: Option<u64>,
+}
+
+impl Meta {
+    /// Picks whichever provider's keys are present
+    fn git(&self) -> Option<GitMeta> {
+        let pr = |id: &Option<String>| id.as_deref().and_then(|s| s.parse().ok());
+        if self.github_commit_sha.is_some() || self.github_repo.is_some() {
+            Some(GitMeta {
+                provider: GitProvider::Github,
+                message: self.github_commit_message.clone(),
+                repo: self.github_repo.clone(),
+                org: self.github_org.clone().or(self.github_commit_org.clone()),
+                branch: self.github_commit_ref.clone(),
+                sha: self.github_commit_sha.clone(),
+                author: self
+                    .github_commit_author_name
+                    .clone()
+                    .or(self.github_commit_author_login.clone()),
+                pr_number: pr(&self.github_pr_id),
+            })
+        } else if self.gitlab_commit_sha.is_some() || self.gitlab_project_name.is_some() {
+            Some(GitMeta {
+                provider: GitProvider::Gitlab,
+                message: self.gitlab_commit_message.clone(),
+                repo: self.gitlab_project_name.clone(),
+                org: self.gitlab_project_namespace.clone(),
+                branch: self.gitlab_commit_ref.clone(),
+                sha: self.gitlab_commit_sha.clone(),
+                author: self
+                    .gitlab_commit_author_name
+                    .clone()
+                    .or(self.gitlab_commit_author_login.clone()),
+                pr_number: pr(&self.gitlab_merge_request_iid),
+            })
+        } else if self.bitbucket_commit_sha.is_some() || self.bitbucket_repo_slug.is_some() {
+            Some(GitMeta {
+                provider: GitProvider::Bitbucket,
+                message: self.bitbucket_commit_message.clone(),
+                // The slug is what appears in URLs; the name may contain spaces
+                repo: self
+                    .bitbucket_repo_slug
+                    .clone()
+                    .or(self.bitbucket_repo_name.clone()),
+                org: self.bitbucket_repo_owner.clone(),
+                branch: self.bitbucket_commit_ref.clone(),
+                sha: self.bitbucket_commit_sha.clone(),
+                author: self.bitbucket_commit_author_name.clone(),
+                pr_number: pr(&self.bitbucket_pr_id),
+            })
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Deserialize)]
 struct VercelResponse {
     deployments: Vec<VercelDeployment>,
+    #[serde(default)]
+    pagination: Option<Pagination>,
+}
+
+#[derive(Deserialize)]
+struct Pagination {
+    /// `until` value for the next (older) page
+    next: Option<u64>,
 }
 
+/// Page limit when fetching a project's history for the dashboard
+const MAX_DASHBOARD_PAGES: usize = 10;
+
 #[derive(Deserialize)]
 struct ProjectsResponse {
     projects: Vec<Project>,
@@ -86,10 +299,37 @@ struct ProjectsResponse {
 pub enum NetworkCommand {
     Deployments(Option<String>), // Optional Project ID
     Projects,
-    Logs(String),        // Deployment ID
-    StartStream(String), // Deployment ID
-    Redeploy(String),    // Deployment ID
-    Cancel(String),      // Deployment ID
+    Logs(String),                    // Deployment ID
+    StartStream(String),             // Deployment ID
+    Redeploy(String),                // Deployment ID
+    Cancel(String),                  // Deployment ID
+    Batch(BatchAction, Vec<String>), // Deployment IDs
+    CompareLogs(String),             // Deployment ID
+    /// Project IDs, fetched back to the start of the period (epoch millis) if given
+    ProjectDeployments(Vec<String>, Option<u64>),
+}
+
+/// An action applied to several marked deployments at once
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BatchAction {
+    Redeploy,
+    Cancel,
+}
+
+impl BatchAction {
+    pub fn display_text(&self) -> &'static str {
+        match self {
+            BatchAction::Redeploy => "Redeploy",
+            BatchAction::Cancel => "Cancel",
+        }
+    }
+
+    pub fn past_tense(&self) -> &'static str {
+        match self {
+            BatchAction::Redeploy => "Redeployed",
+            BatchAction::Cancel => "Canceled",
+        }
+    }
 }
 
 /// Network Manager handles all async API communication
@@ -108,6 +348,8 @@ pub struct Network {
     pub last_log_timestamp: Option<u64>,
     pub initial_project_id: Option<String>,
     pub last_log_id: Option<String>,
+    /// Background fetch of the dashboard history, replaced by each new request
+    pub dashboard_task: Option<tokio::task::JoinHandle<()>>,
 }
 
 impl Network {
@@ -126,6 +368,7 @@ impl Network {
             last_log_timestamp: None,
             initial_project_id,
             last_log_id: None,
+            dashboard_task: None,
         }
     }
 
@@ -178,6 +421,29 @@ impl Network {
                             NetworkCommand::Cancel(id) => {
                                 self.cancel_deployment(id).await;
                             }
+                            NetworkCommand::Batch(action, ids) => {
+                                self.run_batch(action, ids).await;
+                            }
+                            NetworkCommand::CompareLogs(id) => {
+                                self.fetch_compare_logs(id).await;
+                            }
+                            NetworkCommand::ProjectDeployments(ids, since) => {
+                                // Many pages per project: fetch them without blocking the loop,
+                                // dropping a fetch for an older period
+                                if let Some(task) = self.dashboard_task.take() {
+                                    task.abort();
+                                }
+                                let (client, token, sender) =
+                                    (self.client.clone(), self.token.clone(), self.sender.clone());
+                                self.dashboard_task = Some(tokio::spawn(async move {
+                                    for id in ids {
+                                        Self::fetch_project_history(
+                                            &client, &token, &sender, id, since,
+                                        )
+                                        .await;
+                                    }
+                                }));
+                            }
                         }
                     }
                 }
@@ -186,66 +452,38 @@ impl Network {
     }
 
     async fn redeploy_deployment(&self, id: String) {
+        let event = match self.redeploy(&id).await {
+            Ok(()) => NetworkEvent::Info("Redeploy Triggered Successfully".to_string()),
+            Err(e) => NetworkEvent::Error(e),
+        };
+        let _ = self.sender.send(event).await;
+    }
+
+    async fn redeploy(&self, id: &str) -> Result<(), String> {
         // Step 1: Fetch deployment info to get the project name
         let get_url = format!("https://api.vercel.com/v13/deployments/{}", id);
 
-        let get_resp = match self
+        let get_resp = self
             .client
             .get(&get_url)
             .header("Authorization", format!("Bearer {}", self.token))
             .send()
             .await
-        {
-            Ok(r) => r,
-            Err(e) => {
-                let _ = self
-                    .sender
-                    .send(NetworkEvent::Error(format!(
-                        "Redeploy (Get Info) Http Error: {}",
-                        e
-                    )))
-                    .await;
-                return;
-            }
-        };
+            .map_err(|e| format!("Redeploy (Get Info) Http Error: {}", e))?;
 
         if !get_resp.status().is_success() {
-            let _ = self
-                .sender
-                .send(NetworkEvent::Error(format!(
-                    "Redeploy (Get Info) Failed: {}",
-                    get_resp.status()
-                )))
-                .await;
-            return;
+            return Err(format!("Redeploy (Get Info) Failed: {}", get_resp.status()));
         }
 
-        let deployment_info = match get_resp.json::<serde_json::Value>().await {
-            Ok(json) => json,
-            Err(e) => {
-                let _ = self
-                    .sender
-                    .send(NetworkEvent::Error(format!(
-                        "Redeploy (Parse Info) Failed: {}",
-                        e
-                    )))
-                    .await;
-                return;
-            }
-        };
+        let deployment_info = get_resp
+            .json::<serde_json::Value>()
+            .await
+            .map_err(|e| format!("Redeploy (Parse Info) Failed: {}", e))?;
 
-        let name = match deployment_info.get("name").and_then(|v| v.as_str()) {
-            Some(n) => n,
-            None => {
-                let _ = self
-                    .sender
-                    .send(NetworkEvent::Error(
-                        "Redeploy Failed: Could not find project name".to_string(),
-                    ))
-                    .await;
-                return;
-            }
-        };
+        let name = deployment_info
+            .get("name")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| "Redeploy Failed: Could not find project name".to_string())?;
 
         // Step 2: Trigger new deployment using the deploymentId
         let post_url = "https://api.vercel.com/v13/deployments";
@@ -254,88 +492,84 @@ impl Network {
             "deploymentId": id
         });
 
-        let post_resp = match self
+        let post_resp = self
             .client
             .post(post_url)
             .header("Authorization", format!("Bearer {}", self.token))
             .json(&body)
             .send()
             .await
-        {
-            Ok(r) => r,
-            Err(e) => {
-                let _ = self
-                    .sender
-                    .send(NetworkEvent::Error(format!(
-                        "Redeploy (Trigger) Http Error: {}",
-                        e
-                    )))
-                    .await;
-                return;
-            }
-        };
+            .map_err(|e| format!("Redeploy (Trigger) Http Error: {}", e))?;
 
         if !post_resp.status().is_success() {
-            let _ = self
-                .sender
-                .send(NetworkEvent::Error(format!(
-                    "Redeploy Failed: {}",
-                    post_resp.status()
-                )))
-                .await;
-            return;
+            return Err(format!("Redeploy Failed: {}", post_resp.status()));
         }
-
-        let _ = self
-            .sender
-            .send(NetworkEvent::Info(
-                "Redeploy Triggered Successfully".to_string(),
-            ))
-            .await;
+        Ok(())
     }
 
     async fn cancel_deployment(&self, id: String) {
+        let event = match self.cancel(&id).await {
+            Ok(()) => NetworkEvent::Info("Build Cancelled Successfully".to_string()),
+            Err(e) => NetworkEvent::Error(e),
+        };
+        let _ = self.sender.send(event).await;
+    }
+
+    async fn cancel(&self, id: &str) -> Result<(), String> {
         let url = format!("https://api.vercel.com/v13/deployments/{}/cancel", id);
 
-        let resp = match self
+        let resp = self
             .client
             .post(&url)
             .header("Authorization", format!("Bearer {}", self.token))
             .send()
             .await
-        {
-            Ok(r) => r,
-            Err(e) => {
-                let _ = self
-                    .sender
-                    .send(NetworkEvent::Error(format!("Cancel Http Error: {}", e)))
-                    .await;
-                return;
-            }
-        };
+            .map_err(|e| format!("Cancel Http Error: {}", e))?;
 
         if !resp.status().is_success() {
+            return Err(format!("Cancel Failed: {}", resp.status()));
+        }
+        Ok(())
+    }
+
+    /// Runs `action` on each deployment in turn, reporting every result and a final summary
+    async fn run_batch(&self, action: BatchAction, ids: Vec<String>) {
+        let total = ids.len();
+        let mut failures = Vec::new();
+        for (i, id) in ids.into_iter().enumerate() {
+            let result = match action {
+                BatchAction::Redeploy => self.redeploy(&id).await,
+                BatchAction::Cancel => self.cancel(&id).await,
+            };
+            if let Err(e) = &result {
+                failures.push((id.clone(), e.clone()));
+            }
             let _ = self
                 .sender
-                .send(NetworkEvent::Error(format!(
-                    "Cancel Failed: {}",
-                    resp.status()
-                )))
+                .send(NetworkEvent::BatchProgress {
+                    action,
+                    done: i + 1,
+                    total,
+                    id,
+                    result,
+                })
                 .await;
-            return;
         }
-
         let _ = self
             .sender
-            .send(NetworkEvent::Info(
-                "Build Cancelled Successfully".to_string(),
-            ))
+            .send(NetworkEvent::BatchDone {
+                action,
+                total,
+                failures,
+            })
             .await;
     }
 
     async fn fetch_and_send_deployments(&self, project_id: Option<String>) {
-        match self.fetch_deployments(project_id).await {
-            Ok(deployments) => {
+        let page =
+            Self::fetch_deployments(&self.client, &self.token, project_id.as_deref(), None).await;
+        match page {
+            Ok((deployments, _)) => {
                 let _ = self
                     .sender
                     .send(NetworkEvent::Deployments(deployments))
@@ -353,19 +587,64 @@ impl Network {
         }
     }
 
+    /// Fetches every deployment of a project created since `since`, page by page.
+    /// Without `since` only the newest page is fetched.
+    async fn fetch_project_history(
+        client: &reqwest::Client,
+        token: &str,
+        sender: &mpsc::Sender<NetworkEvent>,
+        project_id: String,
+        since: Option<u64>,
+    ) {
+        let mut deployments: Vec<Deployment> = Vec::new();
+        let mut until = None;
+        for _ in 0..MAX_DASHBOARD_PAGES {
+            let page = Self::fetch_deployments(client, token, Some(&project_id), until).await;
+            let (page, next) = match page {
+                Ok(page) => page,
+                Err(e) => {
+                    let _ = sender
+                        .send(NetworkEvent::Error(format!(
+                            "Deployment Fetch Error: {}",
+                            e
+                        )))
+                        .await;
+                    return;
+                }
+            };
+            let covered = match (since, page.last()) {
+                (Some(since), Some(oldest)) => oldest.timestamp < since,
+                _ => true,
+            };
+            deployments.extend(page);
+            match next {
+                Some(next) if !covered => until = Some(next),
+                _ => break,
+            }
+        }
+        let _ = sender
+            .send(NetworkEvent::ProjectDeployments(project_id, deployments))
+            .await;
+    }
+
+    /// One page of deployments, newest first, plus the `until` cursor of the next page
     async fn fetch_deployments(
-        &self,
-        project_id: Option<String>,
-    ) -> Result<Vec<Deployment>, reqwest::Error> {
+        client: &reqwest::Client,
+        token: &str,
+        project_id: Option<&str>,
+        until: Option<u64>,
+    ) -> Result<(Vec<Deployment>, Option<u64>), reqwest::Error> {
         let mut url = "https://api.vercel.com/v6/deployments?limit=100".to_string();
         if let Some(pid) = project_id {
             url.push_str(&format!("&projectId={}", pid));
         }
+        if let Some(until) = until {
+            url.push_str(&format!("&until={}", until));
+        }
 
-        let resp = self
-            .client
+        let resp = client
             .get(&url)
-            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Authorization", format!("Bearer {}", token))
             .send()
             .await?;
 
@@ -375,6 +654,7 @@ impl Network {
         }
 
         let vercel_data: VercelResponse = resp.json().await?;
+        let next = vercel_data.pagination.and_then(|p| p.next);
 
         let deployments = vercel_data
             .deployments
@@ -388,27 +668,14 @@ impl Network {
                     _ => Status::Error,
                 };
 
-                let commit_msg = if let Some(meta) = &d.meta {
-                    meta.github_commit_message
-                        .clone()
-                        .unwrap_or_else(|| "No commit info".to_string())
-                } else {
-                    "No commit info".to_string()
-                };
+                let git = d.meta.as_ref().and_then(Meta::git);
+                let git_field = |f: fn(&GitMeta) -> Option<String>| git.as_ref().and_then(f);
 
-                let repo = if let Some(meta) = &d.meta {
-                    meta.github_repo.clone().unwrap_or_else(|| d.name.clone())
-                } else {
-                    d.name.clone()
-                };
-
-                let branch = if let Some(meta) = &d.meta {
-                    meta.github_commit_ref
-                        .clone()
-                        .unwrap_or_else(|| "main".to_string())
-                } else {
-                    "main".to_string()
-                };
+                let commit_msg = git_field(|g| g.message.clone())
+                    .unwrap_or_else(|| "No commit info".to_string());
+                let repo = git_field(|g| g.repo.clone()).unwrap_or_else(|| d.name.clone());
+                let branch = git_field(|g| g.branch.clone()).unwrap_or_else(|| "main".to_string());
+                let commit_sha = git_field(|g| g.sha.clone());
 
                 let seconds_ago =
                     (chrono::Utc::now().timestamp_millis() as u64).saturating_sub(d.created) / 1000;
@@ -454,11 +721,16 @@ impl Network {
                     creator: d.creator.username,
                     target,
                     short_id,
+                    commit_sha,
+                    provider: git.as_ref().map(|g| g.provider),
+                    org: git_field(|g| g.org.clone()),
+                    commit_author: git_field(|g| g.author.clone()),
+                    pr_number: git.as_ref().and_then(|g| g.pr_number),
                 }
             })
             .collect();
 
-        Ok(deployments)
+        Ok((deployments, next))
     }
 
     async fn fetch_projects(&self) {
@@ -495,6 +767,39 @@ impl Network {
         }
     }
 
+    /// Fetches a deployment's logs for the comparison view. Unlike `fetch_logs` this
+    /// leaves the streaming state alone, so the selected deployment keeps streaming.
+    async fn fetch_compare_logs(&self, deployment_id: String) {
+        let url = format!(
+            "https://api.vercel.com/v2/deployments/{}/events?direction=backward&limit=100",
+            deployment_id
+        );
+        let result = match self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.token))
+            .send()
+            .await
+        {
+            Ok(resp) => resp
+                .json::<Vec<LogEvent>>()
+                .await
+                .map_err(|e| format!("Failed to parse logs for {}: {}", deployment_id, e)),
+            Err(e) => Err(format!("Log Fetch Http Error: {}", e)),
+        };
+        let event = match result {
+            Ok(events) => NetworkEvent::CompareLogs(
+                deployment_id,
+                events
+                    .iter()
+                    .map(|e| LogLine::from_raw(e.created, e.event_type, &e.payload.text))
+                    .collect(),
+            ),
+            Err(msg) => NetworkEvent::Error(msg),
+        };
+        let _ = self.sender.send(event).await;
+    }
+
     async fn fetch_logs(&mut self, deployment_id: String, since: Option<u64>) {
         // Vercel Events API
         let mut url = format!(
@@ -560,9 +865,9 @@ impl Network {
                         }
                     }
 
-                    let logs: Vec<String> = events_to_process
+                    let logs: Vec<LogLine> = events_to_process
                         .iter()
-                        .map(|e| strip_ansi(&e.payload.text))
+                        .map(|e| LogLine::from_raw(e.created, e.event_type, &e.payload.text))
                         .collect();
 
                     if since.is_some() {
@@ -613,38 +918,11 @@ impl Network {
     }
 }
 
-fn strip_ansi(s: &str) -> String {
-    let mut output = String::with_capacity(s.len());
-    let mut inside_escape = false;
-
-    for c in s.chars() {
-        if c == '\x1b' {
-            inside_escape = true;
-            continue;
-        }
-
-        if inside_escape {
-            // ANSI escape sequences typically end with a letter (m, K, H, etc.)
-            if c.is_alphabetic() {
-                inside_escape = false;
-            }
-            // Consume characters inside escape sequence
-            continue;
-        }
-
-        // Also capture carriage returns which can mess up TUI
-        if c == '\r' {
-            continue;
-        }
-
-        output.push(c);
-    }
-    output
-}
-
 #[derive(Deserialize)]
 struct LogEvent {
     id: Option<String>,
+    #[serde(rename = "type", default)]
+    event_type: LogEventType,
     payload: LogPayload,
     created: u64, // Timestamp
 }
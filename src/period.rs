@@ -0,0 +1,362 @@
+use chrono::{Datelike, Duration, Local, NaiveDate, TimeZone};
+
+/// Longest relative period accepted by `parse`, about ten years
+const MAX_DAYS: u32 = 3650;
+
+/// Time range used for filtering the deployment list and computing stats.
+///
+/// Calendar-aligned variants (`Today`, `ThisWeek`, ...) are resolved in the local timezone
+/// every time `bounds` is called, so they roll over at local midnight.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StatPeriod {
+    Last24h,
+    Last7d,
+    Last30d,
+    All,
+    LastHours(u32),
+    LastDays(u32),
+    Today,
+    Yesterday,
+    ThisWeek,
+    LastWeek,
+    ThisMonth,
+    LastMonth,
+    Since(NaiveDate),
+    Between(NaiveDate, NaiveDate), // Inclusive
+}
+
+impl StatPeriod {
+    /// Cycles through the presets. Custom ranges fall back to the first preset.
+    pub fn next(&self) -> Self {
+        match self {
+            StatPeriod::Last24h => StatPeriod::Last7d,
+            StatPeriod::Last7d => StatPeriod::Last30d,
+            StatPeriod::Last30d => StatPeriod::All,
+            _ => StatPeriod::Last24h,
+        }
+    }
+
+    pub fn display_text(&self) -> String {
+        match self {
+            StatPeriod::Last24h => "Last 24h".to_string(),
+            StatPeriod::Last7d => "Last 7d".to_string(),
+            StatPeriod::Last30d => "Last 30d".to_string(),
+            StatPeriod::All => "All Time".to_string(),
+            StatPeriod::LastHours(n) => format!("Last {}h", n),
+            StatPeriod::LastDays(n) => format!("Last {}d", n),
+            StatPeriod::Today => "Today".to_string(),
+            StatPeriod::Yesterday => "Yesterday".to_string(),
+            StatPeriod::ThisWeek => "This Week".to_string(),
+            StatPeriod::LastWeek => "Last Week".to_string(),
+            StatPeriod::ThisMonth => "This Month".to_string(),
+            StatPeriod::LastMonth => "Last Month".to_string(),
+            StatPeriod::Since(d) => format!("Since {}", d.format("%Y-%m-%d")),
+            StatPeriod::Between(a, b) => {
+                format!("{} – {}", a.format("%Y-%m-%d"), b.format("%Y-%m-%d"))
+            }
+        }
+    }
+
+    /// Display text plus the resolved dates for calendar-aligned periods (e.g. "This Week (Oct 12 – Oct 18)").
+    pub fn title_text(&self) -> String {
+        match self.date_span() {
+            Some((a, b)) if a == b => format!("{} ({})", self.display_text(), a.format("%b %d")),
+            Some((a, b)) => format!(
+                "{} ({} – {})",
+                self.display_text(),
+                a.format("%b %d"),
+                b.format("%b %d")
+            ),
+            None => self.display_text(),
+        }
+    }
+
+    /// String stored in `Config.stat_period`. Round-trips through `parse`.
+    pub fn config_string(&self) -> String {
+        match self {
+            StatPeriod::Last24h => "24h".to_string(),
+            StatPeriod::Last7d => "7d".to_string(),
+            StatPeriod::Last30d => "30d".to_string(),
+            StatPeriod::All => "all".to_string(),
+            StatPeriod::LastHours(n) => format!("{}h", n),
+            StatPeriod::LastDays(n) => format!("{}d", n),
+            StatPeriod::Today => "today".to_string(),
+            StatPeriod::Yesterday => "yesterday".to_string(),
+            StatPeriod::ThisWeek => "this week".to_string(),
+            StatPeriod::LastWeek => "last week".to_string(),
+            StatPeriod::ThisMonth => "this month".to_string(),
+            StatPeriod::LastMonth => "last month".to_string(),
+            StatPeriod::Since(d) => format!("since {}", d.format("%Y-%m-%d")),
+            StatPeriod::Between(a, b) => {
+                format!("{}..{}", a.format("%Y-%m-%d"), b.format("%Y-%m-%d"))
+            }
+        }
+    }
+
+    /// Parses user input such as "last 90d", "12h", "yesterday", "this week",
+    /// "since 2026-10-01" or "2026-10-01..2026-10-07".
+    pub fn parse(input: &str) -> Result<StatPeriod, String> {
+        let text = input
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+
+        match text.as_str() {
+            "" => return Err("Enter a period".to_string()),
+            "all" | "all time" => return Ok(StatPeriod::All),
+            "today" => return Ok(StatPeriod::Today),
+            "yesterday" => return Ok(StatPeriod::Yesterday),
+            "this week" => return Ok(StatPeriod::ThisWeek),
+            "last week" => return Ok(StatPeriod::LastWeek),
+            "this month" => return Ok(StatPeriod::ThisMonth),
+            "last month" => return Ok(StatPeriod::LastMonth),
+            _ => {}
+        }
+
+        if let Some(date) = text.strip_prefix("since ") {
+            return parse_date(date).map(StatPeriod::Since);
+        }
+
+        if let Some((a, b)) = text.split_once("..") {
+            let (from, to) = (parse_date(a.trim())?, parse_date(b.trim())?);
+            if from > to {
+                return Err("Range start is after its end".to_string());
+            }
+            return Ok(StatPeriod::Between(from, to));
+        }
+
+        // Relative: "last 90d", "90 days", "12h", "2w"
+        let relative = text.strip_prefix("last ").unwrap_or(&text);
+        let digits_end = relative
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(relative.len());
+        let (number, unit) = relative.split_at(digits_end);
+        let n: u32 = number
+            .parse()
+            .map_err(|_| format!("Unrecognized period: '{}'", input.trim()))?;
+        if n == 0 {
+            return Err("Period must be at least 1".to_string());
+        }
+
+        let too_long = || format!("Period too long (max {} days)", MAX_DAYS);
+        match unit.trim() {
+            "h" | "hour" | "hours" => match n {
+                24 => Ok(StatPeriod::Last24h),
+                _ if n > MAX_DAYS * 24 => Err(too_long()),
+                _ => Ok(StatPeriod::LastHours(n)),
+            },
+            "d" | "day" | "days" => match n {
+                7 => Ok(StatPeriod::Last7d),
+                30 => Ok(StatPeriod::Last30d),
+                _ if n > MAX_DAYS => Err(too_long()),
+                _ => Ok(StatPeriod::LastDays(n)),
+            },
+            "w" | "week" | "weeks" => match n {
+                1 => Ok(StatPeriod::Last7d),
+                _ => match n.checked_mul(7) {
+                    Some(days) if days <= MAX_DAYS => Ok(StatPeriod::LastDays(days)),
+                    _ => Err(too_long()),
+                },
+            },
+            _ => Err(format!("Unknown unit '{}' (use h, d or w)", unit.trim())),
+        }
+    }
+
+    /// Start (inclusive) and end (exclusive) in epoch millis, or `None` for all time.
+    pub fn bounds(&self) -> Option<(u64, u64)> {
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        let hours_back = |h: u64| Some((now.saturating_sub(h * 60 * 60 * 1000), u64::MAX));
+
+        match self {
+            StatPeriod::All => None,
+            StatPeriod::Last24h => hours_back(24),
+            StatPeriod::Last7d => hours_back(7 * 24),
+            StatPeriod::Last30d => hours_back(30 * 24),
+            StatPeriod::LastHours(n) => hours_back(*n as u64),
+            StatPeriod::LastDays(n) => hours_back(*n as u64 * 24),
+            StatPeriod::Since(d) => Some((local_midnight_ms(*d), u64::MAX)),
+            _ => {
+                let (from, to) = self.date_span()?;
+                Some((
+                    local_midnight_ms(from),
+                    local_midnight_ms(to + Duration::days(1)),
+                ))
+            }
+        }
+    }
+
+    pub fn contains(bounds: Option<(u64, u64)>, timestamp: u64) -> bool {
+        bounds.is_none_or(|(start, end)| timestamp >= start && timestamp < end)
+    }
+
+    /// Inclusive local dates covered by calendar-aligned periods.
+    fn date_span(&self) -> Option<(NaiveDate, NaiveDate)> {
+        let today = Local::now().date_naive();
+        let week_start = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+        let month_start = today.with_day(1)?;
+
+        match self {
+            StatPeriod::Today => Some((today, today)),
+            StatPeriod::Yesterday => {
+                let y = today - Duration::days(1);
+                Some((y, y))
+            }
+            StatPeriod::ThisWeek => Some((week_start, today)),
+            StatPeriod::LastWeek => Some((
+                week_start - Duration::days(7),
+                week_start - Duration::days(1),
+            )),
+            StatPeriod::ThisMonth => Some((month_start, today)),
+            StatPeriod::LastMonth => {
+                let last_month_end = month_start - Duration::days(1);
+                Some((last_month_end.with_day(1)?, last_month_end))
+            }
+            StatPeriod::Between(a, b) => Some((*a, *b)),
+            _ => None,
+        }
+    }
+}
+
+fn parse_date(text: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date '{}' (expected YYYY-MM-DD)", text))
+}
+
+/// Epoch millis of local midnight on `date`, tolerating DST gaps.
+fn local_midnight_ms(date: NaiveDate) -> u64 {
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+    match Local.from_local_datetime(&midnight).earliest() {
+        Some(dt) => dt.timestamp_millis().max(0) as u64,
+        None => midnight.and_utc().timestamp_millis().max(0) as u64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn parses_relative_periods() {
+        assert_eq!(StatPeriod::parse("12h"), Ok(StatPeriod::LastHours(12)));
+        assert_eq!(StatPeriod::parse("last 90d"), Ok(StatPeriod::LastDays(90)));
+        assert_eq!(StatPeriod::parse("90 Days"), Ok(StatPeriod::LastDays(90)));
+        assert_eq!(StatPeriod::parse("2w"), Ok(StatPeriod::LastDays(14)));
+    }
+
+    #[test]
+    fn relative_periods_fold_into_presets() {
+        assert_eq!(StatPeriod::parse("24h"), Ok(StatPeriod::Last24h));
+        assert_eq!(StatPeriod::parse("7d"), Ok(StatPeriod::Last7d));
+        assert_eq!(StatPeriod::parse("1 week"), Ok(StatPeriod::Last7d));
+        assert_eq!(StatPeriod::parse("30 days"), Ok(StatPeriod::Last30d));
+    }
+
+    #[test]
+    fn parses_keywords_and_dates() {
+        assert_eq!(
+            StatPeriod::parse("  This   Week "),
+            Ok(StatPeriod::ThisWeek)
+        );
+        assert_eq!(StatPeriod::parse("all time"), Ok(StatPeriod::All));
+        assert_eq!(
+            StatPeriod::parse("since 2026-10-01"),
+            Ok(StatPeriod::Since(date(2026, 10, 1)))
+        );
+        assert_eq!(
+            StatPeriod::parse("2026-10-01 .. 2026-10-07"),
+            Ok(StatPeriod::Between(date(2026, 10, 1), date(2026, 10, 7)))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_periods() {
+        assert_eq!(StatPeriod::parse(" "), Err("Enter a period".to_string()));
+        assert_eq!(
+            StatPeriod::parse("0d"),
+            Err("Period must be at least 1".to_string())
+        );
+        assert_eq!(
+            StatPeriod::parse("3y"),
+            Err("Unknown unit 'y' (use h, d or w)".to_string())
+        );
+        assert_eq!(
+            StatPeriod::parse("soon"),
+            Err("Unrecognized period: 'soon'".to_string())
+        );
+        assert_eq!(
+            StatPeriod::parse("2026-10-07..2026-10-01"),
+            Err("Range start is after its end".to_string())
+        );
+        assert_eq!(
+            StatPeriod::parse("since 2026-13-01"),
+            Err("Invalid date '2026-13-01' (expected YYYY-MM-DD)".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_periods_over_the_limit() {
+        let too_long = Err("Period too long (max 3650 days)".to_string());
+        assert_eq!(StatPeriod::parse("3650d"), Ok(StatPeriod::LastDays(3650)));
+        assert_eq!(StatPeriod::parse("3651d"), too_long);
+        assert_eq!(StatPeriod::parse("87601h"), too_long);
+        assert_eq!(StatPeriod::parse("522w"), too_long);
+        assert_eq!(StatPeriod::parse("4294967295w"), too_long);
+    }
+
+    #[test]
+    fn config_string_round_trips() {
+        for period in [
+            StatPeriod::Last24h,
+            StatPeriod::All,
+            StatPeriod::LastHours(6),
+            StatPeriod::LastDays(90),
+            StatPeriod::LastMonth,
+            StatPeriod::Since(date(2026, 1, 31)),
+            StatPeriod::Between(date(2026, 1, 1), date(2026, 2, 1)),
+        ] {
+            assert_eq!(StatPeriod::parse(&period.config_string()), Ok(period));
+        }
+    }
+
+    #[test]
+    fn relative_bounds_end_in_the_future() {
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        let (start, end) = StatPeriod::LastHours(2).bounds().unwrap();
+        assert_eq!(end, u64::MAX);
+        assert!(now - start >= 2 * 60 * 60 * 1000);
+        assert!(now - start < 2 * 60 * 60 * 1000 + 60_000);
+        assert_eq!(StatPeriod::All.bounds(), None);
+    }
+
+    #[test]
+    fn calendar_bounds_cover_whole_days() {
+        let (start, end) = StatPeriod::Between(date(2026, 3, 1), date(2026, 3, 3))
+            .bounds()
+            .unwrap();
+        assert_eq!(start, local_midnight_ms(date(2026, 3, 1)));
+        assert_eq!(end, local_midnight_ms(date(2026, 3, 4)));
+
+        let (today, _) = StatPeriod::Today.bounds().unwrap();
+        let (yesterday, yesterday_end) = StatPeriod::Yesterday.bounds().unwrap();
+        assert_eq!(yesterday_end, today);
+        assert!(yesterday < today);
+
+        let (this_week, _) = StatPeriod::ThisWeek.bounds().unwrap();
+        let (_, last_week_end) = StatPeriod::LastWeek.bounds().unwrap();
+        assert_eq!(last_week_end, this_week);
+    }
+
+    #[test]
+    fn contains_excludes_the_end() {
+        let bounds = Some((10, 20));
+        assert!(!StatPeriod::contains(bounds, 9));
+        assert!(StatPeriod::contains(bounds, 10));
+        assert!(!StatPeriod::contains(bounds, 20));
+        assert!(StatPeriod::contains(None, 0));
+    }
+}
@@ -2,22 +2,40 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, Padding, Paragraph},
+    widgets::{Block, Borders, Cell, Clear, List, ListItem, Padding, Paragraph, Row, Table},
     Frame,
 };
 
-use crate::app::{ActivePane, App};
-use crate::network::Status;
+use crate::actions::{Action, HelpSection};
+use crate::ansi::AnsiSpan;
+use crate::app::{
+    ActivePane, App, BreakdownColumn, DashboardColumn, DeploymentGroup, ListRow, ViewMode,
+};
+use crate::filter;
+use crate::hyperlink;
+use crate::layout::Pane;
+use crate::logs::{LogEventType, LogLine, LogRow, LogSection, LogTimeMode};
+use crate::network::{BatchAction, Status};
 use crate::theme::ThemeColors;
 
+// Full layout needs 80x28; below that the compact layout is used down to 30x8
+const FULL_MIN_WIDTH: u16 = 80;
+const FULL_MIN_HEIGHT: u16 = 28;
+const COMPACT_MIN_WIDTH: u16 = 30;
+const COMPACT_MIN_HEIGHT: u16 = 8;
+// Width of the log time gutter, including its trailing space
+const TIME_GUTTER_WIDTH: u16 = 10;
+
 // --- MAIN DRAW ---
 pub fn draw(f: &mut Frame, app: &mut App) {
     let size = f.area();
-    // Check for minimum size (80x28)
-    if size.width < 80 || size.height < 28 {
+    // Check for minimum size
+    if size.width < COMPACT_MIN_WIDTH || size.height < COMPACT_MIN_HEIGHT {
         draw_size_warning(f, size, app);
         return;
     }
+    app.is_compact = size.width < FULL_MIN_WIDTH || size.height < FULL_MIN_HEIGHT;
+    app.log_links.clear();
 
     let colors = app.current_theme.get_colors();
 
@@ -28,18 +46,22 @@ pub fn draw(f: &mut Frame, app: &mut App) {
         f.render_widget(bg_block, f.area());
     }
 
-    // Main Layout
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Min(0),    // Body
-        ])
-        .margin(1) // Global padding
-        .split(f.area());
+    if app.is_compact {
+        draw_compact(f, size, app, &colors);
+    } else {
+        // Main Layout
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Header
+                Constraint::Min(0),    // Body
+            ])
+            .margin(1) // Global padding
+            .split(f.area());
 
-    draw_header(f, chunks[0], app, &colors);
-    draw_body(f, chunks[1], app, &colors);
+        draw_header(f, chunks[0], app, &colors);
+        draw_view(f, chunks[1], app, &colors);
+    }
 
     // Theme Selector Overlay
     if app.show_theme_selector {
@@ -51,6 +73,11 @@ pub fn draw(f: &mut Frame, app: &mut App) {
         draw_project_selector(f, app, &colors);
     }
 
+    // Period Input Overlay
+    if app.is_period_input_mode {
+        draw_period_input(f, app, &colors);
+    }
+
     // Error Overlay
     if let Some(err) = &app.error_message {
         draw_error_overlay(f, err, &colors);
@@ -61,21 +88,57 @@ pub fn draw(f: &mut Frame, app: &mut App) {
         draw_context_menu(f, app, &colors);
     }
 
+    // Command Palette Overlay
+    if app.show_command_palette {
+        draw_command_palette(f, app, &colors);
+    }
+
     // Confirmation Toast (Render top-center)
     match &app.confirmation_mode {
         crate::app::ConfirmationState::RedeployPending(_, _) => {
             draw_toast(
                 f,
-                "Press 'r' again to CONFIRM Redeploy",
+                &format!(
+                    "Press '{}' again to CONFIRM Redeploy",
+                    app.keymap.key(Action::Redeploy)
+                ),
                 colors.status_building,
             );
         }
         crate::app::ConfirmationState::CancelPending(_, _) => {
-            draw_toast(f, "Press 'k' again to CONFIRM Cancel", colors.status_error);
+            draw_toast(
+                f,
+                &format!(
+                    "Press '{}' again to CONFIRM Cancel",
+                    app.keymap.key(Action::Cancel)
+                ),
+                colors.status_error,
+            );
+        }
+        crate::app::ConfirmationState::BatchPending(action, ids, _) => {
+            let (key, color) = match action {
+                BatchAction::Redeploy => (app.keymap.key(Action::Redeploy), colors.status_building),
+                BatchAction::Cancel => (app.keymap.key(Action::Cancel), colors.status_error),
+            };
+            draw_toast(
+                f,
+                &format!(
+                    "Press '{}' again to CONFIRM {} of {} deployment(s)",
+                    key,
+                    action.display_text(),
+                    ids.len()
+                ),
+                color,
+            );
         }
         _ => {}
     }
 
+    // Unfinished multi-key sequence
+    if let Some(keys) = app.keymap.pending_label() {
+        draw_toast(f, &format!("{} …", keys), colors.accent_primary);
+    }
+
     // Generic Success/Info Toast
     if let Some((msg, color, _)) = &app.toast_message {
         // Only draw if we aren't showing a confirmation toast (avoid overlap)
@@ -96,6 +159,321 @@ pub fn draw(f: &mut Frame, app: &mut App) {
         );
         draw_key_legend(f, legend_area, app, &colors);
     }
+
+    // Help Overlay
+    if app.show_help {
+        draw_help(f, app, &colors);
+    }
+}
+
+fn draw_view(f: &mut Frame, area: Rect, app: &mut App, colors: &ThemeColors) {
+    match app.view_mode {
+        ViewMode::Deployments if app.show_log_fullscreen => {
+            app.deployments_area = Rect::default();
+            app.deployments_list_area = Rect::default();
+            draw_log_viewer(f, app, area, colors);
+        }
+        ViewMode::Deployments => draw_body(f, area, app, colors),
+        ViewMode::Breakdown => draw_breakdown(f, area, app, colors),
+        ViewMode::Dashboard => draw_dashboard(f, area, app, colors),
+        ViewMode::Branches => draw_branches(f, area, app, colors),
+        ViewMode::Compare => draw_compare(f, area, app, colors),
+    }
+}
+
+// --- COMPACT ---
+fn draw_compact(f: &mut Frame, area: Rect, app: &mut App, colors: &ThemeColors) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Header
+            Constraint::Length(1), // Stats Bar
+            Constraint::Min(0),    // Body
+        ])
+        .split(area);
+
+    let header = Line::from(vec![
+        Span::styled(
+            "Polymer ",
+            Style::default()
+                .fg(colors.text_primary)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            &app.current_project,
+            Style::default().fg(colors.accent_primary),
+        ),
+        Span::styled(
+            format!(" {}", app.current_time),
+            Style::default().fg(colors.text_dim),
+        ),
+    ]);
+    f.render_widget(Paragraph::new(header), chunks[0]);
+
+    let stats = format!(
+        "{} · {} builds · {}% ok · {}s avg · {} active · {} failed",
+        app.stat_period.display_text(),
+        app.total_builds,
+        app.success_rate,
+        app.avg_duration_s,
+        app.active_builds,
+        app.error_count
+    );
+    let stats_color = if app.production_alert().is_some() {
+        colors.status_error
+    } else {
+        colors.text_dim
+    };
+    f.render_widget(
+        Paragraph::new(truncate(&stats, area.width as usize))
+            .style(Style::default().fg(stats_color)),
+        chunks[1],
+    );
+
+    if app.view_mode != ViewMode::Deployments || app.show_log_fullscreen {
+        draw_view(f, chunks[2], app, colors);
+        return;
+    }
+
+    // Filter bar takes one line while active
+    let list_area = if app.is_filter_mode || !app.filter_query.is_empty() {
+        let parts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(chunks[2]);
+        let mut spans = vec![Span::styled(
+            "/",
+            Style::default().fg(colors.accent_primary),
+        )];
+        spans.extend(filter_query_spans(app, colors));
+        if let Some(err) = &app.filter_error {
+            spans.push(Span::styled(
+                format!("  {}", err.message),
+                Style::default().fg(colors.status_error),
+            ));
+        }
+        f.render_widget(Paragraph::new(Line::from(spans)), parts[0]);
+        parts[1]
+    } else {
+        chunks[2]
+    };
+
+    draw_compact_list(f, list_area, app, colors);
+}
+
+/// The filter query with field names highlighted, the first syntax error underlined
+/// and, while typing, a cursor followed by the Tab completion as ghost text
+fn filter_query_spans<'a>(app: &'a App, colors: &ThemeColors) -> Vec<Span<'a>> {
+    let query = app.filter_query.as_str();
+    let error = app.filter_error.as_ref().map(|e| (e.start, e.end));
+    let error_style = Style::default()
+        .fg(colors.status_error)
+        .add_modifier(Modifier::UNDERLINED);
+
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    for token in filter::tokenize(query) {
+        spans.push(Span::raw(&query[pos..token.start]));
+        let text = &query[token.start..token.end];
+        if error.is_some_and(|(start, end)| token.start < end && token.end > start) {
+            spans.push(Span::styled(text, error_style));
+        } else if let Some(split) = token.field.as_ref().and_then(|_| text.find(':')) {
+            spans.push(Span::styled(
+                &text[..=split],
+                Style::default().fg(colors.accent_primary),
+            ));
+            spans.push(Span::styled(
+                &text[split + 1..],
+                Style::default().fg(colors.text_primary),
+            ));
+        } else {
+            spans.push(Span::styled(text, Style::default().fg(colors.text_primary)));
+        }
+        pos = token.end;
+    }
+    spans.push(Span::raw(&query[pos..]));
+
+    if app.is_filter_mode {
+        spans.push(Span::styled(
+            "█",
+            Style::default().fg(colors.accent_primary),
+        ));
+        let completions = filter::completions(query, &app.deployments);
+        if let Some(first) = completions.first() {
+            let typed = filter::tokenize(query)
+                .last()
+                .map(|t| &query[t.start..])
+                .unwrap_or("");
+            let ghost = first
+                .get(typed.len()..)
+                .filter(|_| first.to_lowercase().starts_with(&typed.to_lowercase()))
+                .unwrap_or(first);
+            spans.push(Span::styled(
+                ghost.to_string(),
+                Style::default().fg(colors.text_dim),
+            ));
+            if completions.len() > 1 {
+                spans.push(Span::styled(
+                    format!("  +{} more", completions.len() - 1),
+                    Style::default().fg(colors.text_dim),
+                ));
+            }
+        }
+    }
+    spans
+}
+
+/// One line per deployment: status glyph, id, branch, commit and age
+fn draw_compact_list(f: &mut Frame, area: Rect, app: &mut App, colors: &ThemeColors) {
+    app.deployments_area = area;
+    app.deployments_list_area = area;
+    app.list_row_spans.clear();
+    app.logs_area = Rect::default();
+
+    if app.list_rows.is_empty() {
+        f.render_widget(
+            Paragraph::new("No deployments").style(Style::default().fg(colors.text_dim)),
+            area,
+        );
+        return;
+    }
+
+    let visible_items = (area.height as usize).max(1);
+    let selected_index = app._list_state.selected().unwrap_or(0);
+    if selected_index >= app.scroll_offset + visible_items {
+        app.scroll_offset = selected_index + 1 - visible_items;
+    }
+    if selected_index < app.scroll_offset {
+        app.scroll_offset = selected_index;
+    }
+    // Don't leave empty rows at the bottom after the list shrinks (e.g. folding groups)
+    app.scroll_offset = app
+        .scroll_offset
+        .min(app.list_rows.len().saturating_sub(visible_items));
+
+    let highlight_bg = if app.is_transparent {
+        colors.text_dim
+    } else {
+        colors.border
+    };
+
+    let width = area.width as usize;
+    let has_marks = app.mark_count() > 0;
+    let end = (app.scroll_offset + visible_items).min(app.list_rows.len());
+    for (row, i) in (app.scroll_offset..end).enumerate() {
+        let row_area = Rect::new(area.x, area.y + row as u16, area.width, 1);
+        app.list_row_spans.push((row_area.y, 1, i));
+        let base = if i == selected_index {
+            Style::default().bg(highlight_bg)
+        } else {
+            Style::default()
+        };
+        let Some(d) = app.deployment_at(i) else {
+            if let Some(group) = app.group_at(i) {
+                let mut spans = group_header_spans(app, group, colors, base);
+                let used: usize = spans.iter().map(|s| s.content.chars().count()).sum();
+                spans.push(Span::styled(" ".repeat(width.saturating_sub(used)), base));
+                f.render_widget(Paragraph::new(Line::from(spans)), row_area);
+            }
+            continue;
+        };
+        let (_, status_color) = status_text(&d.status, colors);
+        let glyph = match d.status {
+            Status::Ready => "✔",
+            Status::Error => "✖",
+            Status::Canceled => "○",
+            Status::Building | Status::Initializing => {
+                let frames = ["⠖", "⠲", "⠴", "⠦"];
+                frames[app.spinner_frame % frames.len()]
+            }
+        };
+
+        // Marks get a leading column, only while something is marked
+        let mark = match (app.is_marked(i), has_marks) {
+            (true, _) => "✓",
+            (false, true) => " ",
+            (false, false) => "",
+        };
+
+        // Fixed parts first, the commit message gets what is left
+        let fixed = format!(
+            "{}{} {} {} ",
+            mark,
+            glyph,
+            d.short_id,
+            truncate(&d.branch, 20)
+        );
+        let time = format!(" {}", d.time);
+        let msg_width = width.saturating_sub(fixed.chars().count() + time.chars().count());
+        let msg = truncate(&d.commit_msg, msg_width);
+        let spacer = width
+            .saturating_sub(fixed.chars().count() + msg.chars().count() + time.chars().count());
+
+        let line = Line::from(vec![
+            Span::styled(mark, base.fg(colors.accent_primary)),
+            Span::styled(format!("{} ", glyph), base.fg(status_color)),
+            Span::styled(
+                format!("{} ", d.short_id),
+                base.fg(colors.text_primary).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{} ", truncate(&d.branch, 20)),
+                base.fg(colors.accent_primary),
+            ),
+            Span::styled(msg, base.fg(colors.text_dim)),
+            Span::styled(" ".repeat(spacer), base),
+            Span::styled(time, base.fg(colors.text_dim)),
+        ]);
+
+        f.render_widget(Paragraph::new(line), row_area);
+    }
+}
+
+/// Fold arrow, name and aggregate status of a project or branch group
+fn group_header_spans<'a>(
+    app: &App,
+    group: &'a DeploymentGroup,
+    colors: &ThemeColors,
+    base: Style,
+) -> Vec<Span<'a>> {
+    let arrow = if app.collapsed_groups.contains(&group.key) {
+        "▸"
+    } else {
+        "▾"
+    };
+    let (_, latest_color) = status_text(&group.latest, colors);
+    let mut spans = match &group.branch {
+        None => vec![Span::styled(
+            format!("{} {} ", arrow, group.project),
+            base.fg(colors.text_primary).add_modifier(Modifier::BOLD),
+        )],
+        Some(branch) => vec![
+            Span::styled(format!("  {} ", arrow), base.fg(colors.text_dim)),
+            Span::styled(format!("{} ", branch), base.fg(colors.accent_primary)),
+        ],
+    };
+    spans.push(Span::styled("●", base.fg(latest_color)));
+    spans.push(Span::styled(
+        format!(
+            " {} build{}",
+            group.builds,
+            if group.builds == 1 { "" } else { "s" }
+        ),
+        base.fg(colors.text_dim),
+    ));
+    if group.failed > 0 {
+        spans.push(Span::styled(
+            format!(" · ✖ {}", group.failed),
+            base.fg(colors.status_error),
+        ));
+    }
+    if group.active > 0 {
+        spans.push(Span::styled(
+            format!(" · ⠖ {}", group.active),
+            base.fg(colors.status_building),
+        ));
+    }
+    spans
 }
 
 fn draw_size_warning(f: &mut Frame, area: Rect, app: &App) {
@@ -135,7 +513,10 @@ fn draw_size_warning(f: &mut Frame, area: Rect, app: &App) {
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![Span::styled(
-            "Width = 80 Height = 28",
+            format!(
+                "Width = {} Height = {}",
+                COMPACT_MIN_WIDTH, COMPACT_MIN_HEIGHT
+            ),
             Style::default().fg(colors.status_success),
         )]),
     ];
@@ -184,20 +565,20 @@ fn draw_header(f: &mut Frame, area: Rect, app: &App, colors: &ThemeColors) {
     ]);
     f.render_widget(Paragraph::new(dots_text), layout[1]);
 
-    // Display Github Owner if available
-    let owner = if let Some(d) = app.deployments.first() {
-        if let Some(idx) = d.repo.find('/') {
-            &d.repo[..idx]
-        } else {
-            &d.creator
-        }
-    } else {
-        "Loading..."
+    // Git provider and owner of the loaded deployments, if known
+    let git = app.deployments.iter().find(|d| d.provider.is_some());
+    let provider = git
+        .and_then(|d| d.provider)
+        .map_or("Git", |p| p.display_text());
+    let owner = match (git.and_then(|d| d.org.as_deref()), app.deployments.first()) {
+        (Some(org), _) => org,
+        (None, Some(d)) => &d.creator,
+        (None, None) => "Loading...",
     };
 
     // Metadata (Right Aligned)
     let meta_text = vec![Line::from(vec![
-        Span::styled("Github", Style::default().fg(colors.text_dim)),
+        Span::styled(provider, Style::default().fg(colors.text_dim)),
         Span::styled(" • ", Style::default().fg(colors.border)),
         Span::styled(owner, Style::default().fg(colors.text_dim)),
         Span::raw("     "),
@@ -213,72 +594,949 @@ fn draw_header(f: &mut Frame, area: Rect, app: &App, colors: &ThemeColors) {
     f.render_widget(meta, layout[2]);
 }
 
-// --- BODY ---
-fn draw_body(f: &mut Frame, area: Rect, app: &mut App, colors: &ThemeColors) {
-    let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(50), // Left: Deployments
-            Constraint::Length(1),      // Gutter
-            Constraint::Percentage(50), // Right Side (Stats + Detail + Logs)
-        ])
-        .split(area);
+// --- BODY ---
+fn draw_body(f: &mut Frame, area: Rect, app: &mut App, colors: &ThemeColors) {
+    let layout = app.layout().clone();
+
+    // Hidden panes must not keep stale hit-test areas from a previous layout
+    app.deployments_area = Rect::default();
+    app.deployments_list_area = Rect::default();
+    app.logs_area = Rect::default();
+
+    let (primary_area, secondary_area) =
+        match (layout.primary.is_empty(), layout.secondary.is_empty()) {
+            (false, true) => (area, Rect::default()),
+            (true, false) => (Rect::default(), area),
+            _ => {
+                let split = layout.split_percent.min(100);
+                let direction = if layout.stacked {
+                    Direction::Vertical
+                } else {
+                    Direction::Horizontal
+                };
+                let chunks = Layout::default()
+                    .direction(direction)
+                    .constraints([
+                        Constraint::Percentage(split),       // Primary
+                        Constraint::Length(1),               // Gutter
+                        Constraint::Percentage(100 - split), // Secondary
+                    ])
+                    .split(area);
+                (chunks[0], chunks[2])
+            }
+        };
+
+    draw_pane_group(f, primary_area, &layout.primary, app, colors);
+    draw_pane_group(f, secondary_area, &layout.secondary, app, colors);
+}
+
+/// Stacks a group of panes vertically: fixed panes get their height, flexible panes share the rest by weight
+fn draw_pane_group(
+    f: &mut Frame,
+    area: Rect,
+    slots: &[crate::layout::PaneSlot],
+    app: &mut App,
+    colors: &ThemeColors,
+) {
+    if slots.is_empty() || area.area() == 0 {
+        return;
+    }
+
+    let constraints: Vec<Constraint> = slots
+        .iter()
+        .map(|slot| match slot.pane.fixed_height() {
+            Some(h) => Constraint::Length(h),
+            None => Constraint::Fill(slot.weight.max(1)),
+        })
+        .collect();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(area);
+
+    for (slot, pane_area) in slots.iter().zip(chunks.iter()) {
+        match slot.pane {
+            Pane::Deployments => draw_deployments(f, *pane_area, app, colors),
+            Pane::Domains => draw_domain_box(f, *pane_area, app, colors),
+            Pane::Stats => draw_build_stats(f, app, colors, *pane_area),
+            Pane::Details => draw_details(f, app, colors, *pane_area),
+            Pane::Logs => draw_logs(f, app, *pane_area, colors),
+        }
+    }
+}
+
+fn draw_domain_box(f: &mut Frame, area: Rect, app: &App, colors: &ThemeColors) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(colors.border))
+        .title(" Deployment URLs ")
+        .title_style(Style::default().fg(colors.text_primary))
+        .padding(Padding::new(1, 1, 0, 0)); // tight padding
+
+    let selected_index = app._list_state.selected().unwrap_or(0);
+    let inner_area = block.inner(area);
+    f.render_widget(block, area);
+
+    if let Some(d) = app.deployment_at(selected_index) {
+        let text = vec![
+            Line::from(vec![
+                Span::styled("● ", Style::default().fg(colors.accent_primary)), // Blue dot?
+                Span::styled(
+                    &d.domain,
+                    Style::default()
+                        .fg(colors.accent_primary)
+                        .add_modifier(Modifier::UNDERLINED),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    format!("  {}", d.repo),
+                    Style::default().fg(colors.text_dim),
+                ), // Subtext
+            ]),
+        ];
+        // Right align "Primary" badge?
+        // Left-aligned header text for consistency.
+        f.render_widget(Paragraph::new(text), inner_area);
+
+        // Render badge on right
+        let badge_text = Span::styled("- Primary", Style::default().fg(colors.text_dim));
+        let badge = Paragraph::new(Line::from(badge_text)).alignment(Alignment::Right);
+        f.render_widget(badge, inner_area);
+
+        if app.hyperlinks {
+            let domain_area = Rect::new(inner_area.x + 2, inner_area.y, d.domain.len() as u16, 1);
+            hyperlink::link(
+                f.buffer_mut(),
+                domain_area.intersection(inner_area),
+                &format!("https://{}", d.domain),
+            );
+        }
+    }
+}
+
+// --- BREAKDOWN ---
+fn draw_breakdown(f: &mut Frame, area: Rect, app: &mut App, colors: &ThemeColors) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(colors.accent_primary))
+        .title(Span::styled(
+            format!(
+                " Breakdown by {} ({}) ",
+                app.breakdown_group.display_text(),
+                app.stat_period.title_text()
+            ),
+            Style::default().fg(colors.accent_primary),
+        ))
+        .padding(Padding::new(1, 1, 1, 0));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Table
+            Constraint::Length(1), // Instructions
+        ])
+        .split(inner);
+
+    if app.breakdown_rows.is_empty() {
+        f.render_widget(
+            Paragraph::new("No deployments in this period")
+                .style(Style::default().fg(colors.text_dim)),
+            chunks[0],
+        );
+    } else {
+        // Header labels, with an arrow on the active sort column
+        let labels = [
+            app.breakdown_group.display_text(),
+            "Builds",
+            "Failure Rate",
+            "Median Duration",
+            "Last Status",
+        ];
+        let header_cells: Vec<Cell> = BreakdownColumn::all()
+            .iter()
+            .zip(labels)
+            .enumerate()
+            .map(|(i, (column, label))| {
+                let is_sorted = *column == app.breakdown_sort;
+                let arrow = match (is_sorted, app.breakdown_sort_desc) {
+                    (true, true) => " ↓",
+                    (true, false) => " ↑",
+                    _ => "",
+                };
+                let color = if is_sorted {
+                    colors.accent_primary
+                } else {
+                    colors.text_dim
+                };
+                Cell::from(format!("{} {}{}", i + 1, label, arrow))
+                    .style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+            })
+            .collect();
+
+        let rows: Vec<Row> = app
+            .breakdown_rows
+            .iter()
+            .map(|r| {
+                let (status_label, status_color) = status_text(&r.last_status, colors);
+                let rate_color = if r.failure_rate >= 50 {
+                    colors.status_error
+                } else if r.failure_rate > 0 {
+                    colors.status_building
+                } else {
+                    colors.status_success
+                };
+                Row::new(vec![
+                    Cell::from(r.key.clone()).style(Style::default().fg(colors.text_primary)),
+                    Cell::from(r.builds.to_string())
+                        .style(Style::default().fg(colors.text_primary)),
+                    Cell::from(format!("{}%", r.failure_rate))
+                        .style(Style::default().fg(rate_color)),
+                    Cell::from(format!("{}s", r.median_duration_s))
+                        .style(Style::default().fg(colors.accent_primary)),
+                    Cell::from(format!("● {}", status_label))
+                        .style(Style::default().fg(status_color)),
+                ])
+            })
+            .collect();
+
+        let highlight_bg = if app.is_transparent {
+            colors.text_dim
+        } else {
+            colors.border
+        };
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(20),
+                Constraint::Length(10),
+                Constraint::Length(16),
+                Constraint::Length(19),
+                Constraint::Length(16),
+            ],
+        )
+        .header(Row::new(header_cells).bottom_margin(1))
+        .column_spacing(2)
+        .row_highlight_style(Style::default().bg(highlight_bg).fg(Color::White));
+
+        f.render_stateful_widget(table, chunks[0], &mut app.breakdown_table_state);
+    }
+
+    let instructions = view_hints(&[
+        (sort_keys(app, BreakdownColumn::all().len()), "Sort"),
+        (app.keymap.key(Action::CycleGroup), "Group"),
+        (app.keymap.key(Action::StatPeriod), "Period"),
+        (select_keys(app), "Select"),
+        (app.keymap.key(Action::Select), "Filter Branch"),
+    ]);
+    f.render_widget(
+        Paragraph::new(instructions)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(colors.text_dim)),
+        chunks[1],
+    );
+}
+
+// --- BRANCH MATRIX ---
+fn draw_branches(f: &mut Frame, area: Rect, app: &mut App, colors: &ThemeColors) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(colors.accent_primary))
+        .title(Span::styled(
+            format!(
+                " Branches · {} ({}) ",
+                app.current_project,
+                app.stat_period.title_text()
+            ),
+            Style::default().fg(colors.accent_primary),
+        ))
+        .padding(Padding::new(1, 1, 1, 0));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let show_filter = app.is_filter_mode || !app.filter_query.is_empty();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(if show_filter { 4 } else { 0 }), // Filter + gap
+            Constraint::Min(0),                                  // Table
+            Constraint::Length(1),                               // Instructions
+        ])
+        .split(inner);
+
+    if show_filter {
+        draw_filter_bar(
+            f,
+            Rect {
+                height: 3,
+                ..chunks[0]
+            },
+            app,
+            colors,
+        );
+    }
+
+    // The project column only matters when looking at every project
+    let all_projects = app.current_project_id.is_none();
+
+    if app.branch_rows.is_empty() {
+        f.render_widget(
+            Paragraph::new("No deployments in this period")
+                .style(Style::default().fg(colors.text_dim)),
+            chunks[1],
+        );
+    } else {
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        let mut labels = vec!["Branch", "Latest", "Age", "Author", "Preview", "History"];
+        if all_projects {
+            labels.insert(0, "Project");
+        }
+        let header_cells: Vec<Cell> = labels
+            .into_iter()
+            .map(|label| {
+                Cell::from(label).style(
+                    Style::default()
+                        .fg(colors.text_dim)
+                        .add_modifier(Modifier::BOLD),
+                )
+            })
+            .collect();
+
+        let rows: Vec<Row> = app
+            .branch_rows
+            .iter()
+            .map(|r| {
+                let d = &r.latest;
+                let (status_label, status_color) = status_text(&d.status, colors);
+                // Oldest on the left, so the newest dot sits next to the build count
+                let mut dots: Vec<Span> = r
+                    .recent
+                    .iter()
+                    .rev()
+                    .map(|s| Span::styled("● ", Style::default().fg(status_text(s, colors).1)))
+                    .collect();
+                dots.push(Span::styled(
+                    format!("{} build{}", r.builds, if r.builds == 1 { "" } else { "s" }),
+                    Style::default().fg(colors.text_dim),
+                ));
+
+                let mut cells = vec![
+                    Cell::from(d.branch.clone()).style(Style::default().fg(colors.accent_primary)),
+                    Cell::from(format!("● {}", status_label))
+                        .style(Style::default().fg(status_color)),
+                    Cell::from(format_age(now.saturating_sub(d.timestamp) / 1000))
+                        .style(Style::default().fg(colors.text_dim)),
+                    Cell::from(d.creator.clone()).style(Style::default().fg(colors.text_primary)),
+                    Cell::from(d.domain.clone()).style(Style::default().fg(colors.text_dim)),
+                    Cell::from(Line::from(dots)),
+                ];
+                if all_projects {
+                    cells.insert(
+                        0,
+                        Cell::from(d.name.clone()).style(Style::default().fg(colors.text_primary)),
+                    );
+                }
+                Row::new(cells)
+            })
+            .collect();
+
+        let highlight_bg = if app.is_transparent {
+            colors.text_dim
+        } else {
+            colors.border
+        };
+
+        let mut widths = vec![
+            Constraint::Length(24),
+            Constraint::Length(14),
+            Constraint::Length(5),
+            Constraint::Length(14),
+            Constraint::Fill(1),
+            Constraint::Length(2 * crate::app::BRANCH_HISTORY_DOTS as u16 + 10),
+        ];
+        if all_projects {
+            widths.insert(0, Constraint::Length(16));
+        }
+
+        let table = Table::new(rows, widths)
+            .header(Row::new(header_cells).bottom_margin(1))
+            .column_spacing(2)
+            .row_highlight_style(Style::default().bg(highlight_bg).fg(Color::White));
+
+        f.render_stateful_widget(table, chunks[1], &mut app.branch_table_state);
+    }
+
+    let instructions = view_hints(&[
+        (select_keys(app), "Select"),
+        (app.keymap.key(Action::Select), "History"),
+        (app.keymap.key(Action::OpenBrowser), "Open Preview"),
+        (app.keymap.key(Action::Filter), "Filter"),
+        (app.keymap.key(Action::StatPeriod), "Period"),
+    ]);
+    f.render_widget(
+        Paragraph::new(instructions)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(colors.text_dim)),
+        chunks[2],
+    );
+}
+
+// --- COMPARE ---
+fn draw_compare(f: &mut Frame, area: Rect, app: &mut App, colors: &ThemeColors) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(colors.accent_primary))
+        .title(Span::styled(
+            " Compare Deployments ",
+            Style::default().fg(colors.accent_primary),
+        ))
+        .padding(Padding::new(1, 1, 1, 0));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Report
+            Constraint::Length(1), // Instructions
+        ])
+        .split(inner);
+
+    let Some(c) = &app.comparison else {
+        return;
+    };
+    let heading = |text: &str| {
+        Line::from(Span::styled(
+            text.to_string(),
+            Style::default()
+                .fg(colors.accent_primary)
+                .add_modifier(Modifier::BOLD),
+        ))
+    };
+    let dim = Style::default().fg(colors.text_dim);
+    let primary = Style::default().fg(colors.text_primary);
+    let frames = ["⠖", "⠲", "⠴", "⠦"];
+    let spinner = frames[app.spinner_frame % frames.len()];
+    let mut lines: Vec<Line> = Vec::new();
+
+    // Side by side fields
+    let width = (inner.width as usize).saturating_sub(12) / 2;
+    let row = |label: &str, base: Span<'static>, head: Span<'static>| {
+        let pad = width.saturating_sub(base.content.chars().count());
+        Line::from(vec![
+            Span::styled(format!("{:<12}", label), dim),
+            base,
+            Span::raw(" ".repeat(pad)),
+            head,
+        ])
+    };
+    let field = |text: String, width: usize| Span::styled(truncate(&text, width), primary);
+    let status = |d: &crate::network::Deployment| {
+        let (label, color) = status_text(&d.status, colors);
+        Span::styled(format!("● {}", label), Style::default().fg(color))
+    };
+    let commit = |d: &crate::network::Deployment| {
+        let sha = d
+            .commit_sha
+            .as_deref()
+            .map(crate::compare::short_sha)
+            .unwrap_or_default();
+        format!("{} {}", sha, d.commit_msg).trim().to_string()
+    };
+    let (base, head) = (&c.base, &c.head);
+    let w = width.saturating_sub(2);
+    lines.push(row(
+        "",
+        Span::styled("Base (older)", dim),
+        Span::styled("Head (newer)", dim),
+    ));
+    lines.push(row(
+        "Deployment",
+        Span::styled(base.short_id.clone(), primary.add_modifier(Modifier::BOLD)),
+        Span::styled(head.short_id.clone(), primary.add_modifier(Modifier::BOLD)),
+    ));
+    lines.push(row("Status", status(base), status(head)));
+    lines.push(row(
+        "Target",
+        field(base.target.clone(), w),
+        field(head.target.clone(), w),
+    ));
+    lines.push(row(
+        "Branch",
+        field(base.branch.clone(), w),
+        field(head.branch.clone(), w),
+    ));
+    lines.push(row(
+        "Author",
+        field(base.creator.clone(), w),
+        field(head.creator.clone(), w),
+    ));
+    lines.push(row(
+        "Commit",
+        field(commit(base), w),
+        field(commit(head), w),
+    ));
+    lines.push(row(
+        "Deployed",
+        field(base.time.clone(), w),
+        field(head.time.clone(), w),
+    ));
+
+    // Duration difference
+    let delta = head.duration_ms as i64 - base.duration_ms as i64;
+    let delta_color = if delta.unsigned_abs() * 10 < base.duration_ms {
+        colors.text_dim // Within 10%: noise
+    } else if delta > 0 {
+        colors.status_error
+    } else {
+        colors.status_success
+    };
+    let percent = if base.duration_ms > 0 {
+        format!(", {:+}%", delta * 100 / base.duration_ms as i64)
+    } else {
+        String::new()
+    };
+    lines.push(Line::from(vec![
+        Span::styled(format!("{:<12}", "Duration"), dim),
+        Span::styled(
+            format!(
+                "{} → {}  ",
+                format_duration_ms(base.duration_ms),
+                format_duration_ms(head.duration_ms)
+            ),
+            primary,
+        ),
+        Span::styled(
+            format!("({}{})", format_signed_ms(delta), percent),
+            Style::default().fg(delta_color),
+        ),
+    ]));
+    lines.push(Line::from(""));
+
+    // Commits in between
+    let range = match (&base.commit_sha, &head.commit_sha) {
+        (Some(a), Some(b)) => format!(
+            " {}..{}",
+            crate::compare::short_sha(a),
+            crate::compare::short_sha(b)
+        ),
+        _ => String::new(),
+    };
+    lines.push(heading(&format!(
+        "Commits{} · {} deployed since base",
+        range,
+        c.commits.len()
+    )));
+    if c.commits.is_empty() {
+        lines.push(Line::from(Span::styled("  Same commit", dim)));
+    }
+    for (sha, msg) in &c.commits {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<8}", sha),
+                Style::default().fg(colors.accent_primary),
+            ),
+            Span::styled(msg.clone(), primary),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    // Phase timings
+    lines.push(heading("Phases"));
+    if c.is_loading() {
+        lines.push(Line::from(Span::styled(
+            format!("  {} Loading logs...", spinner),
+            dim,
+        )));
+    } else if c.phases.is_empty() {
+        lines.push(Line::from(Span::styled("  No build phases detected", dim)));
+    }
+    for t in &c.phases {
+        let fmt = |ms: Option<u64>| ms.map_or("—".to_string(), format_duration_ms);
+        let mut spans = vec![
+            Span::styled(format!("  {:<10}", t.phase.display_text()), primary),
+            Span::styled(
+                format!("{:>8} → {:<8}", fmt(t.base_ms), fmt(t.head_ms)),
+                primary,
+            ),
+        ];
+        if let (Some(a), Some(b)) = (t.base_ms, t.head_ms) {
+            let delta = b as i64 - a as i64;
+            let color = if delta.unsigned_abs() < 1000 {
+                colors.text_dim
+            } else if delta > 0 {
+                colors.status_error
+            } else {
+                colors.status_success
+            };
+            spans.push(Span::styled(
+                format!("  {}", format_signed_ms(delta)),
+                Style::default().fg(color),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(""));
+
+    // Log diff: changed lines with a little context, unchanged runs collapsed
+    let removed = c
+        .diff
+        .iter()
+        .filter(|l| l.kind == crate::compare::DiffKind::Removed)
+        .count();
+    let added = c
+        .diff
+        .iter()
+        .filter(|l| l.kind == crate::compare::DiffKind::Added)
+        .count();
+    lines.push(heading(&format!(
+        "Log Diff · -{} +{} (timestamps, durations, hashes and IDs normalized)",
+        removed, added
+    )));
+    if !c.is_loading() && removed + added == 0 {
+        lines.push(Line::from(Span::styled("  Logs are identical", dim)));
+    }
+    const CONTEXT: usize = 2;
+    let near_change = |i: usize| {
+        let from = i.saturating_sub(CONTEXT);
+        let to = (i + CONTEXT + 1).min(c.diff.len());
+        c.diff[from..to]
+            .iter()
+            .any(|l| l.kind != crate::compare::DiffKind::Same)
+    };
+    let mut hidden = 0;
+    for (i, line) in c.diff.iter().enumerate() {
+        if !near_change(i) {
+            hidden += 1;
+            continue;
+        }
+        if hidden > 0 {
+            lines.push(Line::from(Span::styled(
+                format!("  ⋯ {} unchanged lines", hidden),
+                dim,
+            )));
+            hidden = 0;
+        }
+        let (sign, style) = match line.kind {
+            crate::compare::DiffKind::Same => (" ", dim),
+            crate::compare::DiffKind::Removed => ("-", Style::default().fg(colors.status_error)),
+            crate::compare::DiffKind::Added => ("+", Style::default().fg(colors.status_success)),
+        };
+        lines.push(Line::from(Span::styled(
+            format!("{} {}", sign, line.text),
+            style,
+        )));
+    }
+    if hidden > 0 && removed + added > 0 {
+        lines.push(Line::from(Span::styled(
+            format!("  ⋯ {} unchanged lines", hidden),
+            dim,
+        )));
+    }
+
+    // Keep the scroll within the report
+    let max_scroll = (lines.len() as u16).saturating_sub(chunks[0].height);
+    app.compare_scroll = app.compare_scroll.min(max_scroll);
+    f.render_widget(
+        Paragraph::new(lines).scroll((app.compare_scroll, 0)),
+        chunks[0],
+    );
+
+    let instructions = view_hints(&[
+        (select_keys(app), "Scroll"),
+        (
+            format!(
+                "{}/{}",
+                app.keymap.key(Action::PageUp),
+                app.keymap.key(Action::PageDown)
+            ),
+            "Page",
+        ),
+        (
+            format!(
+                "{}/{}",
+                app.keymap.key(Action::Top),
+                app.keymap.key(Action::Bottom)
+            ),
+            "Top/Bottom",
+        ),
+    ]);
+    f.render_widget(
+        Paragraph::new(instructions)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(colors.text_dim)),
+        chunks[1],
+    );
+}
+
+/// "12.3s" or "2m 03s"
+fn format_duration_ms(ms: u64) -> String {
+    if ms < 60_000 {
+        format!("{}.{}s", ms / 1000, ms / 100 % 10)
+    } else {
+        format!("{}m {:02}s", ms / 60_000, ms / 1000 % 60)
+    }
+}
+
+/// Duration difference with an explicit sign: "+4.2s", "-1m 10s"
+fn format_signed_ms(ms: i64) -> String {
+    let sign = if ms < 0 { "-" } else { "+" };
+    format!("{}{}", sign, format_duration_ms(ms.unsigned_abs()))
+}
+
+/// Instruction line of a view: each key with what it does, then `Esc Close`
+fn view_hints(hints: &[(String, &str)]) -> String {
+    hints
+        .iter()
+        .map(|(keys, text)| format!("{} {}", keys, text))
+        .chain(std::iter::once("Esc Close".to_string()))
+        .collect::<Vec<_>>()
+        .join(" │ ")
+}
+
+/// Keys of the up and down actions, e.g. `↑/↓`
+fn select_keys(app: &App) -> String {
+    format!(
+        "{}/{}",
+        app.keymap.key(Action::Up),
+        app.keymap.key(Action::Down)
+    )
+}
+
+/// Keys of the first and last sort column, e.g. `1-6`
+fn sort_keys(app: &App, columns: usize) -> String {
+    format!(
+        "{}-{}",
+        app.keymap.key(Action::SortColumn(1)),
+        app.keymap.key(Action::SortColumn(columns as u8))
+    )
+}
+
+// --- DASHBOARD ---
+fn draw_dashboard(f: &mut Frame, area: Rect, app: &mut App, colors: &ThemeColors) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(colors.accent_primary))
+        .title(Span::styled(
+            format!(" Project Health ({}) ", app.stat_period.title_text()),
+            Style::default().fg(colors.accent_primary),
+        ))
+        .padding(Padding::new(1, 1, 1, 0));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Table
+            Constraint::Length(1), // Instructions
+        ])
+        .split(inner);
+
+    if app.dashboard_rows.is_empty() {
+        let frames = ["⠖", "⠲", "⠴", "⠦"];
+        let spinner = frames[app.spinner_frame % frames.len()];
+        f.render_widget(
+            Paragraph::new(format!("{} Loading projects...", spinner))
+                .style(Style::default().fg(colors.text_dim)),
+            chunks[0],
+        );
+    } else {
+        let labels = [
+            "Project",
+            "Production",
+            "Frequency",
+            "Failure Rate",
+            "Median Build",
+            "Since Success",
+        ];
+        let header_cells: Vec<Cell> = DashboardColumn::all()
+            .iter()
+            .zip(labels)
+            .enumerate()
+            .map(|(i, (column, label))| {
+                let is_sorted = *column == app.dashboard_sort;
+                let arrow = match (is_sorted, app.dashboard_sort_desc) {
+                    (true, true) => " ↓",
+                    (true, false) => " ↑",
+                    _ => "",
+                };
+                let color = if is_sorted {
+                    colors.accent_primary
+                } else {
+                    colors.text_dim
+                };
+                Cell::from(format!("{} {}{}", i + 1, label, arrow))
+                    .style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+            })
+            .collect();
+
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        let rows: Vec<Row> =
+            app.dashboard_rows
+                .iter()
+                .map(|r| {
+                    if !r.loaded {
+                        let dim = Style::default().fg(colors.text_dim);
+                        let mut cells = vec![Cell::from(r.name.clone())
+                            .style(Style::default().fg(colors.text_primary))];
+                        cells.push(Cell::from("Loading...").style(dim));
+                        cells.extend((0..4).map(|_| Cell::from("–").style(dim)));
+                        return Row::new(cells);
+                    }
+
+                    // Production status badge
+                    let badge = match &r.latest_production {
+                        Some(status) => {
+                            let (label, color) = status_text(status, colors);
+                            Line::from(Span::styled(
+                                format!(" {} ", label.to_uppercase()),
+                                Style::default()
+                                    .fg(colors.bg)
+                                    .bg(color)
+                                    .add_modifier(Modifier::BOLD),
+                            ))
+                        }
+                        None => Line::from(Span::styled(
+                            " NO PROD ",
+                            Style::default().fg(colors.text_dim),
+                        )),
+                    };
+
+                    let frequency = if r.deploys_per_day >= 1.0 {
+                        format!("{:.1}/day", r.deploys_per_day)
+                    } else {
+                        format!("{:.1}/wk", r.deploys_per_day * 7.0)
+                    };
+
+                    let rate_color = if r.failure_rate >= 50 {
+                        colors.status_error
+                    } else if r.failure_rate > 0 {
+                        colors.status_building
+                    } else {
+                        colors.status_success
+                    };
+
+                    let (since_text, since_color) = match r.last_production_success {
+                        Some(ts) => {
+                            let age_s = now.saturating_sub(ts) / 1000;
+                            let color = if age_s > 7 * 86400 {
+                                colors.status_error
+                            } else if age_s > 86400 {
+                                colors.status_building
+                            } else {
+                                colors.status_success
+                            };
+                            (format_age(age_s), color)
+                        }
+                        None => ("Never".to_string(), colors.status_error),
+                    };
+
+                    Row::new(vec![
+                        Cell::from(r.name.clone()).style(Style::default().fg(colors.text_primary)),
+                        Cell::from(badge),
+                        Cell::from(frequency).style(Style::default().fg(colors.text_primary)),
+                        Cell::from(format!("{}%", r.failure_rate))
+                            .style(Style::default().fg(rate_color)),
+                        Cell::from(format!("{}s", r.median_duration_s))
+                            .style(Style::default().fg(colors.accent_primary)),
+                        Cell::from(since_text).style(Style::default().fg(since_color)),
+                    ])
+                })
+                .collect();
+
+        let highlight_bg = if app.is_transparent {
+            colors.text_dim
+        } else {
+            colors.border
+        };
 
-    // chunks[0] is Left Side
-    let left_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Min(0),    // List (Flex)
-            Constraint::Length(4), // Domain Box (~3 lines + borders)
-        ])
-        .split(chunks[0]);
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(18),
+                Constraint::Length(16),
+                Constraint::Length(13),
+                Constraint::Length(16),
+                Constraint::Length(16),
+                Constraint::Length(17),
+            ],
+        )
+        .header(Row::new(header_cells).bottom_margin(1))
+        .column_spacing(2)
+        .row_highlight_style(Style::default().bg(highlight_bg).fg(Color::White));
 
-    draw_deployments(f, left_chunks[0], app, colors);
-    draw_domain_box(f, left_chunks[1], app, colors);
+        f.render_stateful_widget(table, chunks[0], &mut app.dashboard_table_state);
+    }
 
-    // chunks[1] is spacer
-    draw_right_panel(f, chunks[2], app, colors);
+    let instructions = view_hints(&[
+        (sort_keys(app, DashboardColumn::all().len()), "Sort"),
+        (app.keymap.key(Action::StatPeriod), "Period"),
+        (select_keys(app), "Select"),
+        (app.keymap.key(Action::Select), "Open Project"),
+    ]);
+    f.render_widget(
+        Paragraph::new(instructions)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(colors.text_dim)),
+        chunks[1],
+    );
 }
 
-fn draw_domain_box(f: &mut Frame, area: Rect, app: &App, colors: &ThemeColors) {
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(colors.border))
-        .title(" Deployment URLs ")
-        .title_style(Style::default().fg(colors.text_primary))
-        .padding(Padding::new(1, 1, 0, 0)); // tight padding
-
-    let selected_index = app._list_state.selected().unwrap_or(0);
-    let inner_area = block.inner(area);
-    f.render_widget(block, area);
+/// Compact age such as "45s", "12m", "3h" or "5d"
+fn format_age(seconds: u64) -> String {
+    if seconds < 60 {
+        format!("{}s", seconds)
+    } else if seconds < 3600 {
+        format!("{}m", seconds / 60)
+    } else if seconds < 86400 {
+        format!("{}h", seconds / 3600)
+    } else {
+        format!("{}d", seconds / 86400)
+    }
+}
 
-    if let Some(d) = app.filtered_deployments.get(selected_index) {
-        let text = vec![
-            Line::from(vec![
-                Span::styled("● ", Style::default().fg(colors.accent_primary)), // Blue dot?
-                Span::styled(
-                    &d.domain,
-                    Style::default()
-                        .fg(colors.accent_primary)
-                        .add_modifier(Modifier::UNDERLINED),
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled(
-                    format!("  {}", d.repo),
-                    Style::default().fg(colors.text_dim),
-                ), // Subtext
-            ]),
-        ];
-        // Right align "Primary" badge?
-        // Left-aligned header text for consistency.
-        f.render_widget(Paragraph::new(text), inner_area);
+fn status_text(status: &Status, colors: &ThemeColors) -> (&'static str, Color) {
+    match status {
+        Status::Ready => ("Successful", colors.status_success),
+        Status::Error => ("Failed", colors.status_error),
+        Status::Building => ("Building", colors.status_building),
+        Status::Canceled => ("Canceled", colors.text_dim),
+        Status::Initializing => ("Initializing", colors.status_building),
+    }
+}
 
-        // Render badge on right
-        let badge_text = Span::styled("- Primary", Style::default().fg(colors.text_dim));
-        let badge = Paragraph::new(Line::from(badge_text)).alignment(Alignment::Right);
-        f.render_widget(badge, inner_area);
+/// Bordered `/` filter input with the query error on the bottom border
+fn draw_filter_bar(f: &mut Frame, r: Rect, app: &App, colors: &ThemeColors) {
+    let border_style = if app.is_filter_mode {
+        Style::default().fg(colors.accent_primary)
+    } else {
+        Style::default().fg(colors.border)
+    };
+    let mut input_block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Filter (Tab complete · Enter/Esc to close) ")
+        .border_style(border_style);
+    if let Some(err) = &app.filter_error {
+        input_block = input_block.title_bottom(
+            Line::from(Span::styled(
+                format!(" {} ", err.message),
+                Style::default().fg(colors.status_error),
+            ))
+            .alignment(Alignment::Right),
+        );
     }
+
+    let input = Paragraph::new(Line::from(filter_query_spans(app, colors))).block(input_block);
+    f.render_widget(input, r);
 }
 
 // --- DEPLOYMENTS ---
@@ -293,11 +1551,24 @@ fn draw_deployments(f: &mut Frame, area: Rect, app: &mut App, colors: &ThemeColo
         colors.border
     };
     // Title update if filtering
-    let title_text = if !app.filter_query.is_empty() || app.is_filter_mode {
+    let mut title_text = if !app.filter_query.is_empty() || app.is_filter_mode {
         format!(" Deployments (Filter: {}) ", app.filter_query)
     } else {
         " Deployments ".to_string()
     };
+    if app.sort_mode != crate::app::SortMode::Newest {
+        title_text.push_str(&format!("· by {} ", app.sort_mode.display_text()));
+    }
+    if app.group_tree {
+        title_text.push_str("· tree ");
+    }
+    if let Some(id) = &app.compare_base {
+        title_text.push_str(&format!("· compare from {} (C) ", app.short_id_of(id)));
+    }
+    let mark_count = app.mark_count();
+    if mark_count > 0 {
+        title_text.push_str(&format!("· {} marked (r/k act, u clear) ", mark_count));
+    }
 
     let block = Block::default()
         .borders(Borders::ALL)
@@ -325,60 +1596,89 @@ fn draw_deployments(f: &mut Frame, area: Rect, app: &mut App, colors: &ThemeColo
 
     // Render Search Bar
     if let Some(r) = search_area {
-        let border_style = if app.is_filter_mode {
-            Style::default().fg(colors.accent_primary)
-        } else {
-            Style::default().fg(colors.border)
-        };
-        let input_block = Block::default()
-            .borders(Borders::ALL)
-            .title(" Filter Branch (Enter/Esc to close) ")
-            .border_style(border_style);
-
-        let query_text = format!("{}█", app.filter_query); // Cursor emulation
-        let input = Paragraph::new(query_text)
-            .style(Style::default().fg(colors.text_primary))
-            .block(input_block);
-
-        f.render_widget(input, r);
+        draw_filter_bar(f, r, app, colors);
     }
 
-    // --- LIST RENDERING using filtered_deployments ---
+    // --- LIST RENDERING using list_rows ---
     let deployments = &app.filtered_deployments;
 
     // Virtual Scrolling
     let visible_height = list_area.height as usize;
-    let total_items = deployments.len();
+    let total_items = app.list_rows.len();
 
-    // Deployment list item rendering logic
-    let item_height = 6;
-
-    let visible_items = (visible_height / item_height).max(1);
+    // Deployments take 5 lines plus a separator, group headers 1 plus a separator
+    let row_height = |row: usize| match app.list_rows[row] {
+        ListRow::Group(_) => 2,
+        ListRow::Deployment(_) => 6,
+    };
+    app.deployments_list_area = list_area;
 
     let selected_index = app._list_state.selected().unwrap_or(0);
 
     // Ensure scroll_offset keeps selected item in view
-    if selected_index >= app.scroll_offset + visible_items {
-        app.scroll_offset = selected_index + 1 - visible_items;
-    }
     if selected_index < app.scroll_offset {
         app.scroll_offset = selected_index;
     }
+    if selected_index < total_items {
+        while app.scroll_offset < selected_index
+            && (app.scroll_offset..=selected_index)
+                .map(row_height)
+                .sum::<usize>()
+                > visible_height
+        {
+            app.scroll_offset += 1;
+        }
+    }
+    // Don't leave empty rows at the bottom after the list shrinks (e.g. folding groups)
+    while app.scroll_offset > 0
+        && (app.scroll_offset - 1..total_items)
+            .map(row_height)
+            .sum::<usize>()
+            <= visible_height
+    {
+        app.scroll_offset -= 1;
+    }
 
     // Draw List
     let mut current_y = list_area.y;
+    let mut row_spans = Vec::new();
 
-    for i in app.scroll_offset..core::cmp::min(app.scroll_offset + visible_items + 1, total_items) {
-        if i >= deployments.len() {
-            break;
-        }
-        let d = &deployments[i];
-
+    for i in app.scroll_offset..total_items {
         if current_y >= list_area.y + list_area.height {
             break;
         }
+        let height = (row_height(i) as u16).min(list_area.y + list_area.height - current_y);
+        row_spans.push((current_y, height, i));
 
         let is_selected = i == selected_index;
+        let d = match app.list_rows[i] {
+            ListRow::Deployment(d) => &deployments[d],
+            ListRow::Group(g) => {
+                let base = if is_selected && app.is_transparent {
+                    Style::default().bg(colors.text_dim)
+                } else if is_selected {
+                    Style::default().bg(colors.border)
+                } else {
+                    Style::default()
+                };
+                let header_area = Rect::new(list_area.x, current_y, list_area.width, 1);
+                f.render_widget(Block::default().style(base), header_area);
+                let spans = group_header_spans(app, &app.deployment_groups[g], colors, base);
+                f.render_widget(Paragraph::new(Line::from(spans)), header_area);
+                current_y += 1;
+                if i < total_items - 1 && current_y < list_area.y + list_area.height {
+                    draw_list_separator(f, app, colors, list_area, current_y);
+                    current_y += 1;
+                }
+                continue;
+            }
+        };
+
+        let mark = if app.is_marked(i) {
+            Span::styled("✓ ", Style::default().fg(colors.accent_primary))
+        } else {
+            Span::raw("  ")
+        };
 
         // Colors
         let name_color = if is_selected && app.is_transparent {
@@ -426,7 +1726,10 @@ fn draw_deployments(f: &mut Frame, area: Rect, app: &mut App, colors: &ThemeColo
             ("/prev", colors.text_dim)
         };
 
-        let left_len = 2 + 2 + d.short_id.len() + 1 + badge_text.len(); // Icon + Space + ID + Space + Badge
+        // Same commit both failed and passed
+        let flaky_text = if app.is_flaky(d) { " ⚑ flaky" } else { "" };
+
+        let left_len = 2 + 2 + d.short_id.len() + 1 + badge_text.len() + flaky_text.chars().count(); // Icon + Space + ID + Space + Badge + Flaky
         let right_len = 2 + status_label.len();
         let spacer_len = (content_width as usize).saturating_sub(left_len + right_len);
 
@@ -458,7 +1761,7 @@ fn draw_deployments(f: &mut Frame, area: Rect, app: &mut App, colors: &ThemeColo
                 1 => {
                     // Line 1
                     Line::from(vec![
-                        Span::raw("  "),
+                        mark.clone(),
                         Span::styled(
                             format!("{} ", final_dot_icon),
                             Style::default().fg(status_color),
@@ -469,6 +1772,7 @@ fn draw_deployments(f: &mut Frame, area: Rect, app: &mut App, colors: &ThemeColo
                         ),
                         Span::raw(" "),
                         Span::styled(badge_text, Style::default().fg(badge_color)),
+                        Span::styled(flaky_text, Style::default().fg(colors.status_building)),
                         Span::raw(" ".repeat(spacer_len)),
                         Span::styled(status_label, Style::default().fg(status_color)),
                         Span::raw("  "),
@@ -506,82 +1810,85 @@ fn draw_deployments(f: &mut Frame, area: Rect, app: &mut App, colors: &ThemeColo
         }
 
         // Render Separator (Not Selected, Not Backgrounded)
-        if i < deployments.len() - 1 && current_y < list_area.y + list_area.height {
-            let sep_area = Rect::new(list_area.x, current_y, list_area.width, 1);
-            // Explicitly use app bg to "cut" any bleed, or just transparent
-            let sep_bg = if app.is_transparent {
-                Color::Reset
-            } else {
-                colors.bg
-            };
-            let separator_char = "─";
-            let separator = Line::from(Span::styled(
-                separator_char.repeat(inner_area.width as usize),
-                Style::default()
-                    .fg(colors.border)
-                    .bg(sep_bg)
-                    .add_modifier(Modifier::DIM),
-            ));
-            f.render_widget(Paragraph::new(separator), sep_area);
+        if i < total_items - 1 && current_y < list_area.y + list_area.height {
+            draw_list_separator(f, app, colors, list_area, current_y);
             current_y += 1;
         }
     }
+    app.list_row_spans = row_spans;
 }
 
-// --- RIGHT PANEL ---
-fn draw_right_panel(f: &mut Frame, area: Rect, app: &mut App, colors: &ThemeColors) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(10), // Stats Banner (Expanded for spacing)
-            Constraint::Min(0),     // Split: Details (Top) + Logs (Bottom)
-        ])
-        .split(area);
-
-    draw_build_stats(f, app, colors, chunks[0]);
-
-    let bottom_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage(30), // Details
-            Constraint::Percentage(70), // Logs (Larger)
-        ])
-        .split(chunks[1]);
-
-    draw_details(f, app, colors, bottom_chunks[0]);
-    draw_logs(f, app, bottom_chunks[1], colors);
+fn draw_list_separator(f: &mut Frame, app: &App, colors: &ThemeColors, list_area: Rect, y: u16) {
+    let sep_area = Rect::new(list_area.x, y, list_area.width, 1);
+    // Explicitly use app bg to "cut" any bleed, or just transparent
+    let sep_bg = if app.is_transparent {
+        Color::Reset
+    } else {
+        colors.bg
+    };
+    let separator_char = "─";
+    let separator = Line::from(Span::styled(
+        separator_char.repeat(list_area.width as usize),
+        Style::default()
+            .fg(colors.border)
+            .bg(sep_bg)
+            .add_modifier(Modifier::DIM),
+    ));
+    f.render_widget(Paragraph::new(separator), sep_area);
 }
 
 // --- BUILD STATS ---
 fn draw_build_stats(f: &mut Frame, app: &mut App, colors: &ThemeColors, area: Rect) {
+    // Production failing for longer than the configured threshold turns the panel red
+    let alert = app.production_alert();
+    let (border_color, title) = match alert {
+        Some(broken_for_s) => (
+            colors.status_error,
+            format!(
+                " Build Overview · ⚠ Production failing for {} ",
+                format_age(broken_for_s)
+            ),
+        ),
+        None => (
+            colors.border,
+            format!(" Build Overview · {} ", app.stat_period.title_text()),
+        ),
+    };
+    let title_color = if alert.is_some() {
+        colors.status_error
+    } else {
+        colors.accent_primary
+    };
+
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(colors.border))
-        .title(Span::styled(
-            " Build Overview ",
-            Style::default().fg(colors.accent_primary),
-        ))
+        .border_style(Style::default().fg(border_color))
+        .title(Span::styled(title, Style::default().fg(title_color)))
         .padding(Padding::new(1, 1, 0, 0));
 
     let inner_area = block.inner(area);
     f.render_widget(block, area);
 
-    // Vertically Center Content (Height 10 -> Inner 8. Content is ~6. 1 top, 1 bottom padding basically)
+    // Vertically Center Content (Height 13 -> Inner 11. Content is ~9. 1 top, 1 bottom padding basically)
     let v_center = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Min(1),    // Top Spacer
-            Constraint::Length(6), // Content (2 rows x 3 lines)
+            Constraint::Length(9), // Content (3 rows x 3 lines)
             Constraint::Min(1),    // Bottom Spacer
         ])
         .split(inner_area);
 
     let content_area = v_center[1];
 
-    // Split into 2 Rows
+    // Split into 3 Rows
     let rows = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+        ])
         .split(content_area);
 
     // Split Row 1
@@ -604,6 +1911,16 @@ fn draw_build_stats(f: &mut Frame, app: &mut App, colors: &ThemeColors, area: Re
         ])
         .split(rows[1]);
 
+    // Split Row 3
+    let row3 = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(33),
+            Constraint::Percentage(33),
+            Constraint::Percentage(33),
+        ])
+        .split(rows[2]);
+
     // Row 1 Metrics
     let count_str = if app.total_builds == 100 && app.deployments.len() >= 100 {
         "100+".to_string()
@@ -656,9 +1973,50 @@ fn draw_build_stats(f: &mut Frame, app: &mut App, colors: &ThemeColors, area: Re
     render_metric(
         f,
         row2[2],
-        "Time Range",
-        app.stat_period.display_text(),
-        colors.text_primary,
+        "Flaky Commits",
+        &format!("{}%", app.flaky_rate),
+        if app.flaky_rate > 0 {
+            colors.status_building
+        } else {
+            colors.text_primary
+        },
+        colors,
+    );
+
+    // Row 3 Metrics (selected branch)
+    let now = chrono::Utc::now().timestamp_millis() as u64;
+    render_metric(
+        f,
+        row3[0],
+        "Time to Recover",
+        &app.mttr_s.map_or("—".to_string(), format_age),
+        colors.accent_primary,
+        colors,
+    );
+    render_metric(
+        f,
+        row3[1],
+        "Fail Streak",
+        &app.failure_streak.to_string(),
+        if app.failure_streak > 0 {
+            colors.status_error
+        } else {
+            colors.status_success
+        },
+        colors,
+    );
+    render_metric(
+        f,
+        row3[2],
+        "Broken Since",
+        &app.broken_since.map_or("—".to_string(), |ts| {
+            format!("{} ago", format_age(now.saturating_sub(ts) / 1000))
+        }),
+        if alert.is_some() {
+            colors.status_error
+        } else {
+            colors.status_building
+        },
         colors,
     );
 }
@@ -705,11 +2063,20 @@ fn draw_details(f: &mut Frame, app: &mut App, colors: &ThemeColors, area: Rect)
 
     let selected_index = app._list_state.selected().unwrap_or(0);
 
-    if let Some(d) = app.filtered_deployments.get(selected_index) {
+    if let Some(d) = app.deployment_at(selected_index) {
         // Calculate max width for content: Area width - Padding (4) - Label ("Commit: " ~8) - Safety (2)
         let max_len = (area.width as usize).saturating_sub(14).max(10);
-
-        let text = vec![
+        let author = d
+            .commit_author
+            .as_ref()
+            .map(|a| format!(" — {}", a))
+            .unwrap_or_default();
+        let pr = d
+            .pr_number
+            .map(|n| format!(" · PR #{}", n))
+            .unwrap_or_default();
+
+        let mut text = vec![
             Line::from(vec![
                 Span::styled("Project: ", Style::default().fg(colors.text_dim)),
                 Span::styled(
@@ -723,16 +2090,21 @@ fn draw_details(f: &mut Frame, app: &mut App, colors: &ThemeColors, area: Rect)
             Line::from(vec![
                 Span::styled("Commit: ", Style::default().fg(colors.text_dim)),
                 Span::styled(
-                    truncate(&d.commit_msg, max_len),
+                    truncate(
+                        &d.commit_msg,
+                        max_len.saturating_sub(author.chars().count()),
+                    ),
                     Style::default().fg(colors.text_primary),
                 ),
+                Span::styled(author, Style::default().fg(colors.text_dim)),
             ]),
             Line::from(vec![
                 Span::styled("Branch: ", Style::default().fg(colors.text_dim)),
                 Span::styled(
-                    truncate(&d.branch, max_len),
+                    truncate(&d.branch, max_len.saturating_sub(pr.chars().count())),
                     Style::default().fg(colors.accent_primary),
                 ),
+                Span::styled(pr, Style::default().fg(colors.text_dim)),
             ]),
             Line::from(""),
             Line::from(vec![
@@ -784,6 +2156,41 @@ fn draw_details(f: &mut Frame, app: &mut App, colors: &ThemeColors, area: Rect)
             ]),
         ];
 
+        // Failure card goes first so it stays visible in small panes
+        if let (Status::Error, Some(summary)) = (&d.status, &app.failure_summary) {
+            let mut card = Vec::new();
+            let mut title = format!("✖ Failed at line {}", summary.line + 1);
+            if let Some(phase) = summary.phase {
+                title.push_str(&format!(" during {}", phase.display_text()));
+            }
+            if let Some(code) = summary.exit_code {
+                title.push_str(&format!(" (exit {})", code));
+            }
+            card.push(Line::from(Span::styled(
+                title,
+                Style::default()
+                    .fg(colors.status_error)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            let excerpt_width = (area.width as usize).saturating_sub(8).max(10);
+            for (i, line) in summary.excerpt.iter().enumerate() {
+                let color = if i == 0 {
+                    colors.text_primary
+                } else {
+                    colors.text_dim
+                };
+                card.push(Line::from(vec![
+                    Span::styled("│ ", Style::default().fg(colors.status_error)),
+                    Span::styled(
+                        truncate(line.trim(), excerpt_width),
+                        Style::default().fg(color),
+                    ),
+                ]));
+            }
+            card.push(Line::from(""));
+            text.splice(0..0, card);
+        }
+
         f.render_widget(Paragraph::new(text).block(block), area);
     } else {
         f.render_widget(
@@ -805,7 +2212,8 @@ fn draw_logs(f: &mut Frame, app: &mut App, area: Rect, colors: &ThemeColors) {
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color))
         .border_type(ratatui::widgets::BorderType::Rounded)
-        .title(" Build Logs ")
+        .title(format!(" Build Logs{} ", log_filter_tags(app)))
+        .title(Line::from(log_follow_indicator(app, colors)).alignment(Alignment::Right))
         .title_style(Style::default().fg(colors.text_primary))
         .padding(Padding::new(1, 1, 1, 1));
 
@@ -824,15 +2232,26 @@ fn draw_logs(f: &mut Frame, app: &mut App, area: Rect, colors: &ThemeColors) {
         return;
     }
 
-    if app.logs.is_empty() {
+    if app.logs.is_empty() || app.log_rows.is_empty() {
+        let msg = if app.logs.is_empty() {
+            "No logs available"
+        } else {
+            "All lines hidden by filters (v / i)"
+        };
         f.render_widget(
-            Paragraph::new("No logs available").style(Style::default().fg(colors.text_dim)),
+            Paragraph::new(msg).style(Style::default().fg(colors.text_dim)),
             inner,
         );
         return;
     }
 
-    let inner_width = inner.width.saturating_sub(4).max(10) as usize; // -2 for bullet, -2 for safety
+    let time_width = if app.log_time_mode == LogTimeMode::Off {
+        0
+    } else {
+        TIME_GUTTER_WIDTH
+    };
+    let inner_width = inner.width.saturating_sub(4 + time_width).max(10) as usize; // -2 for bullet, -2 for safety
+    let build_start = app.log_build_start();
 
     // Optimization: Only regex highlight visible items
     // Calculate visible window approximation
@@ -842,14 +2261,33 @@ fn draw_logs(f: &mut Frame, app: &mut App, area: Rect, colors: &ThemeColors) {
     let start_window = selected_idx.saturating_sub(window_height);
     let end_window = selected_idx.saturating_add(window_height);
 
+    // Lines in the V range selection get a tinted background
+    let range = app.log_range();
+    let range_style = |idx: usize| match range {
+        Some((from, to)) if idx >= from && idx <= to => Style::default().bg(colors.border),
+        _ => Style::default(),
+    };
+
     // Creates the ListItems
     let items: Vec<ListItem> = app
-        .logs
+        .log_rows
         .iter()
         .enumerate()
-        .map(|(idx, msg)| {
-            let is_visible = idx >= start_window && idx <= end_window;
-            let is_selected = Some(idx) == app.log_list_state.selected();
+        .map(|(pos, row)| {
+            let idx = match *row {
+                LogRow::Section(s) => {
+                    return ListItem::new(log_section_header(
+                        &app.log_sections[s],
+                        app.collapsed_phases.contains(&app.log_sections[s].phase),
+                        colors,
+                    ))
+                }
+                LogRow::Line(i) => i,
+            };
+            let log_line = &app.logs[idx];
+            let msg = &log_line.text;
+            let is_visible = pos >= start_window && pos <= end_window;
+            let is_selected = Some(pos) == app.log_list_state.selected();
 
             // Bullet Color logic (Always needed for visual consistency)
             let dot_palette = [
@@ -862,11 +2300,41 @@ fn draw_logs(f: &mut Frame, app: &mut App, area: Rect, colors: &ThemeColors) {
             let lower = msg.to_lowercase();
             if lower.contains("error") || lower.contains("fail") {
                 dot_color = colors.status_error;
+            } else if log_line.event_type == LogEventType::Stderr {
+                dot_color = colors.status_building;
             }
+            let time_gutter =
+                log_time_gutter(app.log_time_mode, &app.logs, idx, build_start, colors);
 
-            let wrapped_lines = wrap_text(msg, inner_width);
             let mut lines = Vec::new();
 
+            // Native colors: wrap the styled runs directly so they survive line breaks
+            if is_visible && app.native_log_colors && !log_line.ansi.is_empty() {
+                let spans = ansi_spans(msg, &log_line.ansi, colors);
+                for (i, mut row) in wrap_spans(spans, inner_width).into_iter().enumerate() {
+                    if is_selected {
+                        for span in &mut row {
+                            if span.style.fg == Some(colors.text_dim)
+                                || span.style.fg == Some(colors.text_primary)
+                            {
+                                span.style = span.style.fg(Color::White);
+                            }
+                        }
+                    }
+                    let prefix = if i == 0 {
+                        Span::styled("● ", Style::default().fg(dot_color))
+                    } else {
+                        Span::raw("  ")
+                    };
+                    row.insert(0, prefix);
+                    lines.push(Line::from(row));
+                }
+                prepend_time_gutter(&mut lines, time_gutter);
+                return ListItem::new(lines).style(range_style(idx));
+            }
+
+            let wrapped_lines = wrap_text(msg, inner_width);
+
             if wrapped_lines.is_empty() {
                 let mut spans = if is_visible {
                     highlight_line(msg, &app.log_regex, colors)
@@ -923,23 +2391,484 @@ fn draw_logs(f: &mut Frame, app: &mut App, area: Rect, colors: &ThemeColors) {
                 }
             }
 
-            ListItem::new(lines)
+            prepend_time_gutter(&mut lines, time_gutter);
+            ListItem::new(lines).style(range_style(idx))
+        })
+        .collect();
+
+    // Style logic: if transparent, use text_dim for bg (subtle), else use border color ?
+    let highlight_bg = if app.is_transparent {
+        colors.text_dim
+    } else {
+        colors.border
+    };
+    let highlight_style = Style::default().bg(highlight_bg).fg(Color::White);
+
+    let list = List::new(items)
+        .highlight_symbol("")
+        .highlight_style(highlight_style);
+
+    f.render_stateful_widget(list, inner, &mut app.log_list_state);
+    app.log_links = hyperlink::link_urls(f.buffer_mut(), inner, app.hyperlinks);
+}
+
+/// Full-screen log viewer: line-number gutter, search overlay and a prompt/status line
+fn draw_log_viewer(f: &mut Frame, app: &mut App, area: Rect, colors: &ThemeColors) {
+    let short_id = app
+        .deployment_at(app._list_state.selected().unwrap_or(0))
+        .map(|d| d.short_id.clone())
+        .unwrap_or_default();
+    let position = match app.selected_log_line() {
+        Some(i) => format!(" Ln {}/{} ", i + 1, app.logs.len()),
+        None => String::new(),
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(colors.accent_primary))
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .title(format!(
+            " Build Logs · {}{} ",
+            short_id,
+            log_filter_tags(app)
+        ))
+        .title(
+            Line::from(vec![log_follow_indicator(app, colors), Span::raw(position)])
+                .alignment(Alignment::Right),
+        )
+        .title_style(Style::default().fg(colors.text_primary));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+    app.logs_area = chunks[0];
+
+    draw_log_status_line(f, app, chunks[1], colors);
+
+    if app.is_loading_logs {
+        let frames = ["⠖", "⠲", "⠴", "⠦"];
+        let spinner = frames[app.spinner_frame % frames.len()];
+        f.render_widget(
+            Paragraph::new(format!("{} Loading logs...", spinner))
+                .style(Style::default().fg(colors.text_dim)),
+            chunks[0],
+        );
+        return;
+    }
+
+    if app.logs.is_empty() || app.log_rows.is_empty() {
+        let msg = if app.logs.is_empty() {
+            "No logs available"
+        } else {
+            "All lines hidden by filters (v / i)"
+        };
+        f.render_widget(
+            Paragraph::new(msg).style(Style::default().fg(colors.text_dim)),
+            chunks[0],
+        );
+        return;
+    }
+
+    let gutter_width = app.logs.len().to_string().len();
+    let time_width = if app.log_time_mode == LogTimeMode::Off {
+        0
+    } else {
+        TIME_GUTTER_WIDTH as usize
+    };
+    let text_width = (chunks[0].width as usize)
+        .saturating_sub(gutter_width + 3 + time_width)
+        .max(10);
+    let build_start = app.log_build_start();
+    let match_style = Style::default()
+        .bg(colors.status_building)
+        .fg(Color::Black)
+        .add_modifier(Modifier::BOLD);
+
+    let range = app.log_range();
+    let range_style = |idx: usize| match range {
+        Some((from, to)) if idx >= from && idx <= to => Style::default().bg(colors.border),
+        _ => Style::default(),
+    };
+
+    // Only highlight lines near the selection, like the logs pane
+    let selected_idx = app.log_list_state.selected().unwrap_or(0);
+    let window_height = area.height as usize * 2;
+    let start_window = selected_idx.saturating_sub(window_height);
+    let end_window = selected_idx.saturating_add(window_height);
+
+    let items: Vec<ListItem> = app
+        .log_rows
+        .iter()
+        .enumerate()
+        .map(|(pos, row)| {
+            let idx = match *row {
+                LogRow::Section(s) => {
+                    let mut header = log_section_header(
+                        &app.log_sections[s],
+                        app.collapsed_phases.contains(&app.log_sections[s].phase),
+                        colors,
+                    );
+                    header.spans.insert(
+                        0,
+                        Span::styled(
+                            format!("{:>w$} │ ", "", w = gutter_width),
+                            Style::default().fg(colors.text_dim),
+                        ),
+                    );
+                    return ListItem::new(header);
+                }
+                LogRow::Line(i) => i,
+            };
+            let log_line = &app.logs[idx];
+            let msg = &log_line.text;
+            let is_visible = pos >= start_window && pos <= end_window;
+            let is_hit = app.log_search_hits.binary_search(&pos).is_ok();
+
+            let spans = match (&app.log_search_regex, is_visible) {
+                (Some(re), true) if is_hit => overlay_matches(
+                    log_line_spans(
+                        app.native_log_colors,
+                        msg,
+                        &log_line.ansi,
+                        &app.log_regex,
+                        colors,
+                    ),
+                    msg,
+                    re,
+                    match_style,
+                ),
+                (_, true) => log_line_spans(
+                    app.native_log_colors,
+                    msg,
+                    &log_line.ansi,
+                    &app.log_regex,
+                    colors,
+                ),
+                _ => vec![Span::styled(
+                    msg.clone(),
+                    Style::default().fg(colors.text_dim),
+                )],
+            };
+
+            let gutter_color = if is_hit {
+                colors.status_building
+            } else {
+                colors.text_dim
+            };
+            let mut lines: Vec<Line> = wrap_spans(spans, text_width)
+                .into_iter()
+                .map(Line::from)
+                .collect();
+            prepend_time_gutter(
+                &mut lines,
+                log_time_gutter(app.log_time_mode, &app.logs, idx, build_start, colors),
+            );
+            for (i, line) in lines.iter_mut().enumerate() {
+                let gutter = if i == 0 {
+                    format!("{:>w$} │ ", idx + 1, w = gutter_width)
+                } else {
+                    format!("{:>w$} │ ", "", w = gutter_width)
+                };
+                line.spans
+                    .insert(0, Span::styled(gutter, Style::default().fg(gutter_color)));
+            }
+
+            ListItem::new(lines).style(range_style(idx))
         })
         .collect();
 
-    // Style logic: if transparent, use text_dim for bg (subtle), else use border color ?
     let highlight_bg = if app.is_transparent {
         colors.text_dim
     } else {
         colors.border
     };
-    let highlight_style = Style::default().bg(highlight_bg).fg(Color::White);
-
     let list = List::new(items)
         .highlight_symbol("")
-        .highlight_style(highlight_style);
+        .highlight_style(Style::default().bg(highlight_bg));
 
-    f.render_stateful_widget(list, inner, &mut app.log_list_state);
+    f.render_stateful_widget(list, chunks[0], &mut app.log_list_state);
+    app.log_links = hyperlink::link_urls(f.buffer_mut(), chunks[0], app.hyperlinks);
+}
+
+/// Time gutter for log line `idx`; deltas are colored by how long the step took
+fn log_time_gutter<'a>(
+    mode: LogTimeMode,
+    logs: &[LogLine],
+    idx: usize,
+    build_start: u64,
+    colors: &ThemeColors,
+) -> Option<Span<'a>> {
+    let timestamp = logs[idx].timestamp;
+    let previous = idx.checked_sub(1).map(|p| logs[p].timestamp);
+    let text = mode.format(timestamp, previous, build_start)?;
+
+    let color = match (mode, previous) {
+        (LogTimeMode::Delta, Some(prev)) => match timestamp.saturating_sub(prev) {
+            ms if ms >= 10_000 => colors.status_error,
+            ms if ms >= 2_000 => colors.status_building,
+            _ => colors.text_dim,
+        },
+        _ => colors.text_dim,
+    };
+    Some(Span::styled(
+        format!("{} ", text),
+        Style::default().fg(color),
+    ))
+}
+
+/// Puts the time gutter in front of the first row and pads the wrapped rows below it
+fn prepend_time_gutter<'a>(lines: &mut [Line<'a>], gutter: Option<Span<'a>>) {
+    let Some(gutter) = gutter else {
+        return;
+    };
+    for (i, line) in lines.iter_mut().enumerate() {
+        let span = if i == 0 {
+            gutter.clone()
+        } else {
+            Span::raw(" ".repeat(TIME_GUTTER_WIDTH as usize))
+        };
+        line.spans.insert(0, span);
+    }
+}
+
+/// Collapsible header for a build phase: fold marker, name, line count and elapsed time
+fn log_section_header<'a>(section: &LogSection, collapsed: bool, colors: &ThemeColors) -> Line<'a> {
+    let marker = if collapsed { "▸" } else { "▾" };
+    Line::from(vec![
+        Span::styled(
+            format!("{} {}", marker, section.phase.display_text()),
+            Style::default()
+                .fg(colors.accent_primary)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                "  {} lines · {}",
+                section.line_count(),
+                format_age(section.elapsed_ms / 1000)
+            ),
+            Style::default().fg(colors.text_dim),
+        ),
+    ])
+}
+
+/// "● Following" while tailing, "↓ N new lines" once paused (empty otherwise)
+fn log_follow_indicator<'a>(app: &App, colors: &ThemeColors) -> Span<'a> {
+    if app.log_follow {
+        Span::styled(" ● Following ", Style::default().fg(colors.status_success))
+    } else if app.log_new_lines > 0 {
+        Span::styled(
+            format!(
+                " ↓ {} new line{} (F) ",
+                app.log_new_lines,
+                if app.log_new_lines == 1 { "" } else { "s" }
+            ),
+            Style::default()
+                .fg(colors.status_building)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else {
+        Span::raw("")
+    }
+}
+
+/// Active log filters for pane titles, e.g. " · warnings & errors"
+fn log_filter_tags(app: &App) -> String {
+    let mut tags = String::new();
+    if app.log_problems_only {
+        tags.push_str(" · warnings & errors");
+    }
+    if app.hide_install_noise {
+        tags.push_str(" · install hidden");
+    }
+    tags
+}
+
+/// Bottom line of the log viewer: search/jump prompt, hit counter or key hints
+fn draw_log_status_line(f: &mut Frame, app: &App, area: Rect, colors: &ThemeColors) {
+    let hit_counter = || match (app.log_search_hit_index, app.log_search_hits.len()) {
+        (_, 0) => Span::styled("  no matches", Style::default().fg(colors.status_error)),
+        (Some(i), n) => Span::styled(
+            format!("  {}/{}", i + 1, n),
+            Style::default().fg(colors.status_building),
+        ),
+        (None, n) => Span::styled(
+            format!("  {} matches", n),
+            Style::default().fg(colors.status_building),
+        ),
+    };
+
+    let line = if app.is_log_search_mode {
+        let mut spans = vec![Span::styled(
+            format!("/{}█", app.log_search_query),
+            Style::default().fg(colors.accent_primary),
+        )];
+        if let Some(err) = &app.log_search_error {
+            spans.push(Span::styled(
+                format!("  {}", err),
+                Style::default().fg(colors.status_error),
+            ));
+        } else if !app.log_search_query.is_empty() {
+            spans.push(hit_counter());
+        }
+        Line::from(spans)
+    } else if app.is_log_jump_mode {
+        Line::from(Span::styled(
+            format!(":{}█", app.log_jump_input),
+            Style::default().fg(colors.accent_primary),
+        ))
+    } else if let Some((from, to)) = app.log_range() {
+        Line::from(vec![
+            Span::styled(
+                format!("Lines {}-{} selected", from + 1, to + 1),
+                Style::default().fg(colors.text_primary),
+            ),
+            Span::styled(
+                "  y copy · V/Esc clear",
+                Style::default().fg(colors.text_dim),
+            ),
+        ])
+    } else if !app.log_search_query.is_empty() {
+        Line::from(vec![
+            Span::styled(
+                format!("/{}", app.log_search_query),
+                Style::default().fg(colors.text_primary),
+            ),
+            hit_counter(),
+            Span::styled(
+                "  n/N next/prev · Esc clear",
+                Style::default().fg(colors.text_dim),
+            ),
+        ])
+    } else {
+        Line::from(Span::styled(
+            "/ search · :N line · v levels · i install · z/Z fold · f/Esc close",
+            Style::default().fg(colors.text_dim),
+        ))
+    };
+
+    f.render_widget(Paragraph::new(line), area);
+}
+
+/// Re-styles the parts of `spans` matched by `regex`. `spans` must concatenate to `text`.
+fn overlay_matches<'a>(
+    spans: Vec<Span<'a>>,
+    text: &str,
+    regex: &regex::Regex,
+    style: Style,
+) -> Vec<Span<'a>> {
+    let ranges: Vec<(usize, usize)> = regex
+        .find_iter(text)
+        .filter(|m| !m.is_empty())
+        .map(|m| (m.start(), m.end()))
+        .collect();
+    if ranges.is_empty() {
+        return spans;
+    }
+
+    let mut result = Vec::new();
+    let mut offset = 0;
+    for span in spans {
+        let content = span.content.to_string();
+        let end = offset + content.len();
+
+        // Split the span at every match boundary that falls inside it
+        let mut cuts = vec![0, content.len()];
+        for &(start, stop) in &ranges {
+            for b in [start, stop] {
+                if b > offset && b < end {
+                    cuts.push(b - offset);
+                }
+            }
+        }
+        cuts.sort_unstable();
+        cuts.dedup();
+
+        for w in cuts.windows(2) {
+            let abs = offset + w[0];
+            let is_match = ranges
+                .iter()
+                .any(|&(start, stop)| abs >= start && abs < stop);
+            let piece_style = if is_match {
+                span.style.patch(style)
+            } else {
+                span.style
+            };
+            result.push(Span::styled(content[w[0]..w[1]].to_string(), piece_style));
+        }
+        offset = end;
+    }
+    result
+}
+
+/// Hard-wraps styled spans at `width` characters, keeping styles across breaks
+fn wrap_spans(spans: Vec<Span<'_>>, width: usize) -> Vec<Vec<Span<'_>>> {
+    let width = width.max(1);
+    let mut lines = vec![Vec::new()];
+    let mut used = 0;
+
+    for span in spans {
+        let content = span.content.to_string();
+        let mut rest = content.as_str();
+        while !rest.is_empty() {
+            if used == width {
+                lines.push(Vec::new());
+                used = 0;
+            }
+            let take = rest
+                .char_indices()
+                .nth(width - used)
+                .map(|(i, _)| i)
+                .unwrap_or(rest.len());
+            let (head, tail) = rest.split_at(take);
+            used += head.chars().count();
+            if let Some(line) = lines.last_mut() {
+                line.push(Span::styled(head.to_string(), span.style));
+            }
+            rest = tail;
+        }
+    }
+    lines
+}
+
+/// Spans for a log line: the build's own colors when enabled and present, else `highlight_line`
+fn log_line_spans<'a>(
+    native: bool,
+    text: &str,
+    ansi: &[AnsiSpan],
+    regex: &regex::Regex,
+    colors: &ThemeColors,
+) -> Vec<Span<'a>> {
+    if native && !ansi.is_empty() {
+        ansi_spans(text, ansi, colors)
+    } else {
+        highlight_line(text, regex, colors)
+    }
+}
+
+/// Converts SGR runs into spans covering all of `text`
+fn ansi_spans<'a>(text: &str, runs: &[AnsiSpan], colors: &ThemeColors) -> Vec<Span<'a>> {
+    let plain = Style::default().fg(colors.text_primary);
+    let mut spans = Vec::new();
+    let mut last = 0;
+    for run in runs {
+        if run.start > last {
+            spans.push(Span::styled(text[last..run.start].to_string(), plain));
+        }
+        spans.push(Span::styled(
+            text[run.start..run.end].to_string(),
+            run.style.to_style(colors),
+        ));
+        last = run.end;
+    }
+    if last < text.len() {
+        spans.push(Span::styled(text[last..].to_string(), plain));
+    }
+    spans
 }
 
 fn highlight_line<'a>(text: &str, regex: &regex::Regex, colors: &ThemeColors) -> Vec<Span<'a>> {
@@ -1174,7 +3103,13 @@ fn draw_theme_selector(f: &mut Frame, app: &mut App, colors: &ThemeColors) {
 
     // Bottom Bar with Transparency Toggle
     let checkbox = if app.is_transparent { "[x]" } else { "[ ]" };
-    let instructions = format!("{} Transp. (X) │ ↕ Select │ ↵ Close", checkbox);
+    let instructions = format!(
+        "{} Transp. ({}) │ {} Select │ {} Close",
+        checkbox,
+        app.keymap.key(Action::Transparency),
+        select_keys(app),
+        app.keymap.key(Action::Select)
+    );
     let p = Paragraph::new(instructions)
         .alignment(Alignment::Center)
         .style(Style::default().fg(colors.text_dim));
@@ -1238,13 +3173,384 @@ fn draw_project_selector(f: &mut Frame, app: &mut App, colors: &ThemeColors) {
     f.render_stateful_widget(list, chunks[0], &mut app.project_list_state);
 
     let checkbox = if app.is_transparent { "[x]" } else { "[ ]" };
-    let instructions = format!("{} Transp. (Space) │ ↕ Navigate │ ↵ Select", checkbox);
+    let instructions = format!(
+        "{} Transp. ({}) │ {} Navigate │ {} Select",
+        checkbox,
+        app.keymap.key(Action::Transparency),
+        select_keys(app),
+        app.keymap.key(Action::Select)
+    );
     let p = Paragraph::new(instructions)
         .alignment(Alignment::Center)
         .style(Style::default().fg(colors.text_dim));
     f.render_widget(p, chunks[1]);
 }
 
+// --- COMMAND PALETTE ---
+fn draw_command_palette(f: &mut Frame, app: &mut App, colors: &ThemeColors) {
+    let area = centered_rect(60, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let bg_color = if app.is_transparent {
+        Color::Reset
+    } else {
+        colors.bg
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(colors.accent_primary))
+        .title(" Commands ")
+        .style(Style::default().bg(bg_color).fg(colors.text_primary));
+
+    f.render_widget(block.clone(), area);
+
+    let inner = block.inner(area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Query
+            Constraint::Min(0),
+            Constraint::Length(1), // Instructions
+        ])
+        .split(inner);
+
+    let query = Line::from(vec![
+        Span::styled(" : ", Style::default().fg(colors.accent_primary)),
+        Span::raw(format!("{}█", app.palette_query)), // Cursor emulation
+    ]);
+    f.render_widget(Paragraph::new(query), chunks[0]);
+
+    let highlight_bg = if app.is_transparent {
+        colors.text_dim
+    } else {
+        colors.border
+    };
+    // Padding and highlight symbol take 4 columns
+    let width = chunks[1].width.saturating_sub(4) as usize;
+    let items: Vec<ListItem> = if app.palette_matches.is_empty() {
+        vec![ListItem::new(Span::styled(
+            "No matching commands",
+            Style::default().fg(colors.text_dim),
+        ))]
+    } else {
+        app.palette_matches
+            .iter()
+            .map(|action| {
+                let recent = if app.recent_actions.contains(action) {
+                    "recent  "
+                } else {
+                    ""
+                };
+                let key = app.keymap.label(*action);
+                let title = truncate(
+                    action.title(),
+                    width.saturating_sub(recent.len() + key.chars().count() + 1),
+                );
+                let pad = width
+                    .saturating_sub(title.chars().count() + recent.len() + key.chars().count());
+                ListItem::new(Line::from(vec![
+                    Span::styled(title, Style::default().fg(colors.text_primary)),
+                    Span::raw(" ".repeat(pad)),
+                    Span::styled(recent, Style::default().fg(colors.text_dim)),
+                    Span::styled(
+                        key,
+                        Style::default()
+                            .fg(colors.accent_primary)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                ]))
+            })
+            .collect()
+    };
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::NONE)
+                .padding(Padding::new(1, 1, 0, 0)),
+        )
+        .highlight_symbol("> ")
+        .highlight_style(Style::default().bg(highlight_bg));
+
+    f.render_stateful_widget(list, chunks[1], &mut app.palette_list_state);
+
+    let p = Paragraph::new("↕ Select │ ↵ Run │ Esc Close")
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(colors.text_dim));
+    f.render_widget(p, chunks[2]);
+}
+
+// --- HELP ---
+// Width of the key column in the help overlay
+const HELP_KEY_WIDTH: usize = 20;
+
+/// Fixed keys of a help section that aren't in the keymap: (keys, description, needs
+/// confirmation)
+fn fixed_help_entries(section: HelpSection, app: &App) -> Vec<(String, String, bool)> {
+    let entry = |keys: &str, text: &str| (keys.to_string(), text.to_string(), false);
+    let key = |action| {
+        let keys = app.keymap.label(action);
+        if keys.is_empty() {
+            "unbound".to_string()
+        } else {
+            keys
+        }
+    };
+    let key_in_prompt = |action| {
+        let keys = app.keymap.prompt_label(action);
+        if keys.is_empty() {
+            "unbound".to_string()
+        } else {
+            keys
+        }
+    };
+    let nav = format!("{}, {}", key(Action::Up), key(Action::Down));
+    match section {
+        HelpSection::General => vec![entry(
+            "Esc",
+            "Dismiss errors, ranges, a compare base and pending confirmations",
+        )],
+        HelpSection::Deployments | HelpSection::Logs => vec![],
+        HelpSection::LogViewer => vec![entry("Esc", "Clear the search, then close the viewer")],
+        HelpSection::Prompts => vec![
+            entry(
+                "Enter",
+                "Apply and close the prompt (filter and search stay active)",
+            ),
+            entry("Esc", "Clear and close the prompt"),
+            entry("Tab", "Complete the filter field or value"),
+            entry("↑ / ↓", "Pick a command in the command palette"),
+            entry(&key_in_prompt(Action::Help), "Show this help"),
+        ],
+        HelpSection::Selectors => vec![
+            entry(&nav, "Move through the list; themes preview as you go"),
+            entry(
+                &key(Action::Select),
+                "Switch to the selected project, or keep the theme",
+            ),
+            entry(
+                "Esc",
+                &format!(
+                    "Close the selector ({} and {} also close theirs)",
+                    app.keymap.key(Action::Themes),
+                    app.keymap.key(Action::Projects)
+                ),
+            ),
+            entry(&key(Action::Help), "Show this help"),
+        ],
+        HelpSection::ContextMenu => vec![
+            entry(&nav, "Move through the entries"),
+            entry(&key(Action::Select), "Run the selected entry (or click it)"),
+            entry("Open in Browser", "Open the deployment URL"),
+            (
+                "Redeploy".to_string(),
+                format!(
+                    "Redeploy this deployment from the same commit, like {}",
+                    app.keymap.key(Action::Redeploy)
+                ),
+                true,
+            ),
+            (
+                "Kill".to_string(),
+                format!(
+                    "Cancel this running build, like {}",
+                    app.keymap.key(Action::Cancel)
+                ),
+                true,
+            ),
+            entry(
+                "Copy …",
+                "Copy the URL, ID, commit message or selected log lines",
+            ),
+            entry("Esc", "Close the menu"),
+            entry(&key(Action::Help), "Show this help"),
+        ],
+        HelpSection::Views => vec![
+            entry(&nav, "Move through the rows, or scroll the compare view"),
+            entry(
+                &key(Action::Select),
+                "Open the selected branch or project in the deployment list",
+            ),
+            entry(&key(Action::Filter), "Filter the branch matrix"),
+            entry(
+                &key(Action::OpenBrowser),
+                "Open the branch's latest preview (branch matrix)",
+            ),
+            entry(&key(Action::StatPeriod), "Cycle the statistics period"),
+            entry("Esc", "Back to the deployment list (or the view's own key)"),
+            entry(&key(Action::Help), "Show this help"),
+        ],
+    }
+}
+
+fn draw_help(f: &mut Frame, app: &mut App, colors: &ThemeColors) {
+    let area = centered_rect(80, 85, f.area());
+    f.render_widget(Clear, area);
+
+    let bg_color = if app.is_transparent {
+        Color::Reset
+    } else {
+        colors.bg
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(colors.accent_primary))
+        .title(" Help ")
+        .title_bottom(Line::from(" ↕ Scroll │ Esc Close ").centered())
+        .style(Style::default().bg(bg_color).fg(colors.text_primary))
+        .padding(Padding::new(1, 1, 0, 0));
+
+    let width = block.inner(area).width as usize;
+    let mut lines = vec![
+        Line::from(Span::styled(
+            truncate(
+                "▶ where help was opened · [confirm] needs a second press · keys follow the keymap",
+                width,
+            ),
+            Style::default().fg(colors.text_dim),
+        )),
+        Line::from(""),
+    ];
+    let mut first_active = None;
+    for &section in HelpSection::all() {
+        let active = app.help_sections.contains(&section);
+        if active && first_active.is_none() {
+            first_active = Some(lines.len());
+        }
+        lines.push(if active {
+            Line::from(vec![
+                Span::styled(
+                    format!("▶ {}", section.title()),
+                    Style::default()
+                        .fg(colors.accent_primary)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("  (current)", Style::default().fg(colors.text_dim)),
+            ])
+        } else {
+            Line::from(Span::styled(
+                format!("  {}", section.title()),
+                Style::default()
+                    .fg(colors.text_primary)
+                    .add_modifier(Modifier::BOLD),
+            ))
+        });
+
+        let mut entries: Vec<(String, String, bool)> = Action::all()
+            .iter()
+            .filter(|a| a.help_section() == section)
+            .map(|a| {
+                let keys = app.keymap.label(*a);
+                let keys = if keys.is_empty() {
+                    "unbound".to_string()
+                } else {
+                    keys
+                };
+                (keys, a.description().to_string(), a.needs_confirmation())
+            })
+            .collect();
+        entries.extend(fixed_help_entries(section, app));
+
+        let key_color = if active {
+            colors.accent_primary
+        } else {
+            colors.text_dim
+        };
+        for (keys, text, confirm) in entries {
+            let tag = if confirm { "  [confirm]" } else { "" };
+            let text = truncate(&text, width.saturating_sub(HELP_KEY_WIDTH + 4 + tag.len()));
+            let mut spans = vec![
+                Span::styled(
+                    format!(
+                        "    {:<width$}",
+                        truncate(&keys, HELP_KEY_WIDTH - 1),
+                        width = HELP_KEY_WIDTH
+                    ),
+                    Style::default().fg(key_color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(text, Style::default().fg(colors.text_primary)),
+            ];
+            spans.push(Span::styled(
+                tag,
+                Style::default().fg(colors.status_building),
+            ));
+            lines.push(Line::from(spans));
+        }
+        lines.push(Line::from(""));
+    }
+
+    let inner_height = block.inner(area).height as usize;
+    let max_scroll = lines.len().saturating_sub(inner_height) as u16;
+    if app.help_scroll_to_active {
+        app.help_scroll = first_active.unwrap_or(0).saturating_sub(2) as u16;
+        app.help_scroll_to_active = false;
+    }
+    app.help_scroll = app.help_scroll.min(max_scroll);
+
+    let p = Paragraph::new(lines)
+        .block(block)
+        .scroll((app.help_scroll, 0));
+    f.render_widget(p, area);
+}
+
+// --- PERIOD INPUT ---
+fn draw_period_input(f: &mut Frame, app: &App, colors: &ThemeColors) {
+    let area = f.area();
+    let width = 60.min(area.width);
+    let height = 7;
+    let rect = Rect::new(
+        (area.width.saturating_sub(width)) / 2,
+        (area.height.saturating_sub(height)) / 2,
+        width,
+        height.min(area.height),
+    );
+    f.render_widget(Clear, rect);
+
+    let bg_color = if app.is_transparent {
+        Color::Reset
+    } else {
+        colors.bg
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(colors.accent_primary))
+        .title(" Time Range (Enter/Esc to close) ")
+        .style(Style::default().bg(bg_color).fg(colors.text_primary))
+        .padding(Padding::new(1, 1, 0, 0));
+
+    let status_line = match &app.period_input_error {
+        Some(err) => Line::from(Span::styled(
+            err.as_str(),
+            Style::default().fg(colors.status_error),
+        )),
+        None => match crate::app::StatPeriod::parse(&app.period_input) {
+            Ok(period) => Line::from(vec![
+                Span::styled("→ ", Style::default().fg(colors.text_dim)),
+                Span::styled(
+                    period.title_text(),
+                    Style::default().fg(colors.status_success),
+                ),
+            ]),
+            Err(_) => Line::from(""),
+        },
+    };
+
+    let text = vec![
+        Line::from(format!("{}█", app.period_input)), // Cursor emulation
+        status_line,
+        Line::from(""),
+        Line::from(Span::styled(
+            "e.g. last 90d, 12h, yesterday, this week, since 2026-10-01",
+            Style::default().fg(colors.text_dim),
+        )),
+    ];
+
+    f.render_widget(Paragraph::new(text).block(block), rect);
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -1296,24 +3602,34 @@ fn draw_key_legend(f: &mut Frame, area: Rect, app: &App, colors: &ThemeColors) {
 
     let mouse_status = if app.enable_mouse { "ON" } else { "OFF" };
 
-    // Items to show
-    let items = vec![
-        ("Theme", "T"),
-        ("Open in Browser", "O"),
-        ("Change Timerange", "S"),
-        ("Projects", "P"),
-        ("Mouse Interaction", mouse_status), // Toggle M
-        ("Quit", "Q"),
+    // Items to show, with their keys from the active keymap
+    let items = [
+        ("Help", Action::Help),
+        ("Commands", Action::Palette),
+        ("Theme", Action::Themes),
+        ("Open in Browser", Action::OpenBrowser),
+        ("Change Timerange", Action::StatPeriod),
+        ("Custom Range", Action::CustomPeriod),
+        ("Projects", Action::Projects),
+        ("Breakdown", Action::Breakdown),
+        ("Dashboard", Action::Dashboard),
+        ("Layout", Action::Layout),
+        ("Log Viewer", Action::LogViewer),
+        ("Mouse Interaction", Action::Mouse),
+        ("Quit", Action::Quit),
     ];
 
     let mut spans = vec![];
-    for (label, key) in items {
+    for (label, action) in items {
+        let Some(key) = app.keymap.keys_for(action).into_iter().next() else {
+            continue;
+        };
         spans.push(Span::styled(
             format!(" {} ", label),
             Style::default().fg(colors.text_primary),
         ));
-        let key_text = if label == "Mouse Interaction" {
-            format!("(M [{}])", key)
+        let key_text = if action == Action::Mouse {
+            format!("({} [{}])", key, mouse_status)
         } else {
             format!("({})", key)
         };
@@ -1375,7 +3691,7 @@ fn draw_context_menu(f: &mut Frame, app: &App, colors: &ThemeColors) {
                 } else {
                     Style::default().fg(colors.text_primary)
                 };
-                ListItem::new(Span::styled(format!(" {} ", opt), style))
+                ListItem::new(Span::styled(format!(" {} ", opt.menu_title()), style))
             })
             .collect();
 